@@ -1,6 +1,7 @@
 use tui::backend::CrosstermBackend;
 use tui::Terminal;
 
+use crossterm::cursor::Show;
 use crossterm::event::{poll, read, DisableMouseCapture, EnableMouseCapture, Event};
 use crossterm::execute;
 use crossterm::terminal::{
@@ -8,98 +9,244 @@ use crossterm::terminal::{
 };
 
 use std::io::{stdout, Stdout};
+use std::panic;
 use std::time::Duration;
 
 use mongodb::sync::Database;
 
-use crate::menus::{ContentsMenu, FeedsMenu, Menu, MenuState, StoriesMenu};
+use crate::config::{Config, LayoutMode};
+use crate::download::Downloader;
+use crate::notifier::Notifier;
+use crate::undo::UndoStack;
+
+use crate::menus::{
+    set_theme, ContentsMenu, ErrorPopup, FeedsMenu, Menu, MenuState, PanesMenu, StatusBar,
+    StoriesMenu,
+};
 
 pub struct App<'a> {
     pub feeds_menu: FeedsMenu<'a>,
     pub stories_menu: StoriesMenu<'a>,
-    pub contents_menu: ContentsMenu,
+    pub contents_menu: ContentsMenu<'a>,
+    pub panes_menu: PanesMenu<'a>,
+
+    pub error_popup: ErrorPopup<'a>,
+    pub status_bar: StatusBar,
 
     pub current_menu: MenuState,
+
+    config: &'a Config,
+    downloader: &'a Downloader,
+    undo: &'a UndoStack,
+    notifier: &'a Notifier,
 }
 
 impl<'a> App<'a> {
-    pub fn new(db: &'a Database) -> Self {
+    pub fn new(
+        db: &'a Database,
+        config: &'a Config,
+        downloader: &'a Downloader,
+        undo: &'a UndoStack,
+        notifier: &'a Notifier,
+    ) -> Self {
+        set_theme(config.interface.theme);
         App {
-            feeds_menu: FeedsMenu::new(db),
-            stories_menu: StoriesMenu::new(db),
-            contents_menu: ContentsMenu::new(),
-
-            current_menu: MenuState::Feeds,
+            feeds_menu: FeedsMenu::new(db, config, downloader, undo, notifier),
+            stories_menu: StoriesMenu::new(db, config, downloader, undo, notifier),
+            contents_menu: ContentsMenu::new(db, config, downloader, undo, notifier),
+            panes_menu: PanesMenu::new(db, config, downloader, undo, notifier),
+
+            error_popup: ErrorPopup::new(),
+            status_bar: StatusBar::new(),
+
+            current_menu: match config.interface.layout {
+                LayoutMode::Menus => MenuState::Feeds,
+                LayoutMode::Panes => MenuState::Panes,
+            },
+
+            config,
+            downloader,
+            undo,
+            notifier,
         }
     }
 
     pub fn load(&mut self) -> crate::error::Result<()> {
-        self.feeds_menu.reload()
+        match self.current_menu {
+            MenuState::Panes => self.panes_menu.reload(),
+            _ => self.feeds_menu.reload(),
+        }
     }
 
-    fn ui<M: Menu>(menu: &mut M, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> MenuState {
+    fn ui<M: Menu>(
+        menu: &mut M,
+        error_popup: &mut ErrorPopup,
+        status_bar: &mut StatusBar,
+        notifier: &Notifier,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    ) -> crate::error::Result<MenuState> {
         // EXTRA RUNNING IN THE BACKGROUND ex. POPUP
-        menu.observer();
+        menu.observer()?;
 
-        // RENDERING MENU
-        terminal.draw(|f| menu.draw(f)).unwrap();
+        // RENDERING MENU, all of it again after a program had the terminal
+        if notifier.take_terminal_lent() {
+            terminal.clear()?;
+        }
+        terminal.draw(|f| {
+            menu.draw(f);
+            status_bar.draw(f);
+            if error_popup.popped {
+                error_popup.draw(f);
+            }
+        })?;
 
         // READING KEYBOARD at 100 millis
-        if let Ok(_) = poll(Duration::from_millis(100)) {
-            if let Ok(Event::Key(key_event)) = read() {
-                return menu.transition(key_event);
+        if poll(Duration::from_millis(100))? {
+            match read()? {
+                // An error on screen swallows keys and clicks until dismissed
+                Event::Key(key_event) => {
+                    if error_popup.popped {
+                        error_popup.transition(key_event)?;
+                    } else {
+                        return menu.transition(key_event);
+                    }
+                }
+                Event::Mouse(mouse_event) => {
+                    if error_popup.popped {
+                        error_popup.mouse(mouse_event)?;
+                    } else {
+                        return menu.mouse(mouse_event);
+                    }
+                }
+                // Drawn again for the new size right away instead of on the next key
+                Event::Resize(..) => terminal.autoresize()?,
+                _ => {}
             }
         }
 
-        menu.state()
+        Ok(menu.state())
     }
 
-    pub fn run(&mut self) -> crate::error::Result<()> {
-        enable_raw_mode().unwrap();
-        let mut stdout = stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture).unwrap();
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend).unwrap();
-
-        loop {
-            self.current_menu = match &self.current_menu {
-                // TRANSITION FOR FEEDS MENU
-                MenuState::Feeds => Self::ui(&mut self.feeds_menu, &mut terminal),
-
-                // TRANSITION FOR STORIES MENU
-                MenuState::Stories(feed) => {
-                    if let Some(feed) = feed.to_owned() {
-                        self.stories_menu.set_feed(feed);
-                        self.feeds_menu.reload()?;
-                    }
-                    Self::ui(&mut self.stories_menu, &mut terminal)
+    fn step(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    ) -> crate::error::Result<MenuState> {
+        let state = match &self.current_menu {
+            // TRANSITION FOR FEEDS MENU
+            MenuState::Feeds => Self::ui(
+                &mut self.feeds_menu,
+                &mut self.error_popup,
+                &mut self.status_bar,
+                self.notifier,
+                terminal,
+            ),
+
+            // TRANSITION FOR STORIES MENU
+            MenuState::Stories(feed) => {
+                if let Some(feed) = feed.to_owned() {
+                    self.stories_menu.set_feed(*feed);
+                    self.feeds_menu.reload()?;
                 }
+                Self::ui(
+                    &mut self.stories_menu,
+                    &mut self.error_popup,
+                    &mut self.status_bar,
+                    self.notifier,
+                    terminal,
+                )
+            }
 
-                // TRANSITION FOR CONTENTS MENU
-                MenuState::Contents(story) => {
-                    if let Some(story) = story.to_owned() {
-                        self.contents_menu.set_story(story);
-                        self.stories_menu.reload()?;
-                    }
-                    Self::ui(&mut self.contents_menu, &mut terminal)
+            // TRANSITION FOR CONTENTS MENU
+            MenuState::Contents(story) => {
+                if let Some(story) = story.to_owned() {
+                    self.contents_menu.set_story(*story);
+                    self.stories_menu.reload()?;
+                    self.contents_menu
+                        .set_feed(self.stories_menu.feed().clone());
                 }
+                Self::ui(
+                    &mut self.contents_menu,
+                    &mut self.error_popup,
+                    &mut self.status_bar,
+                    self.notifier,
+                    terminal,
+                )
+            }
 
-                MenuState::Exit => {
-                    break;
-                }
-            };
+            // ALL AT ONCE
+            MenuState::Panes => Self::ui(
+                &mut self.panes_menu,
+                &mut self.error_popup,
+                &mut self.status_bar,
+                self.notifier,
+                terminal,
+            ),
+
+            MenuState::Exit => Ok(MenuState::Exit),
+        }?;
+
+        // The reading view can move on to other stories, even in other feeds
+        if matches!(state, MenuState::Stories(None))
+            && matches!(self.current_menu, MenuState::Contents(_))
+        {
+            self.stories_menu
+                .set_feed(self.contents_menu.feed().clone());
+            self.stories_menu.select_story(self.contents_menu.story());
+        }
+
+        if let Some(message) = self.undo.take_message() {
+            self.status_bar.info(message);
+        }
+        if let Some(message) = self.notifier.take_message() {
+            self.status_bar.info(message);
         }
+        // Downloads fail in the background, the stories list only marks them with [!]
+        if let Some(failure) = self.downloader.take_failures().pop() {
+            self.status_bar.error(failure);
+        }
+
+        // Feeds could have been added or read from the other menus
+        if matches!(state, MenuState::Feeds) && !matches!(self.current_menu, MenuState::Feeds) {
+            self.feeds_menu.reload()?;
+        }
+        Ok(state)
+    }
 
-        // restore terminal
-        disable_raw_mode().unwrap();
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )
-        .unwrap();
-        terminal.show_cursor().unwrap();
+    pub fn run(&mut self) -> crate::error::Result<()> {
+        // Leave the terminal usable even if something panics while in raw mode
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let _ = restore_terminal();
+            default_hook(info);
+        }));
+
+        enable_raw_mode()?;
+        let mut stdout = stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        if self.config.interface.mouse {
+            execute!(stdout, EnableMouseCapture)?;
+        }
+        let backend = CrosstermBackend::new(stdout);
+        let mut terminal = Terminal::new(backend)?;
+
+        while !matches!(self.current_menu, MenuState::Exit) {
+            match self.step(&mut terminal) {
+                Ok(state) => self.current_menu = state,
+                Err(error) => {
+                    // Keep the current menu, but do not enter it a second time
+                    self.current_menu = self.current_menu.settled();
+                    self.status_bar.error(error.to_string());
+                    self.error_popup.show(&error);
+                }
+            }
+        }
 
+        restore_terminal()?;
         Ok(())
     }
 }
+
+fn restore_terminal() -> std::io::Result<()> {
+    disable_raw_mode()?;
+    execute!(stdout(), LeaveAlternateScreen, DisableMouseCapture, Show)
+}
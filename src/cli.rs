@@ -1,8 +1,18 @@
-use mongodb::sync::Client;
+use std::fs;
+use std::path::PathBuf;
+
+use mongodb::sync::{Client, Database};
 
 use clap::{Parser, Subcommand};
 
-use crate::{models::insert_many_feed, util::fetch_feed};
+use chrono::Utc;
+
+use crate::config::Config;
+use crate::download::Downloader;
+use crate::models::{find_many_feed, insert_many_feed, replace_one_feed, Duplicates};
+use crate::notifier::Notifier;
+use crate::undo::UndoStack;
+use crate::util::{export_opml, fetch_feed, fetch_feed_retrying, notify_new_stories, refresh_feed};
 
 #[derive(Parser)]
 #[command(author = "Dragoş Ionescu")]
@@ -22,6 +32,10 @@ pub struct Cli {
     /// Insert some default Feeds in the database
     #[arg(long)]
     pub seed: bool,
+
+    /// Read settings from this file instead of the default config.toml
+    #[arg(short, long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -48,6 +62,27 @@ pub enum Commands {
         #[arg(short, long, action = clap::ArgAction::Count)]
         verbose: u8,
     },
+
+    /// Fetch every subscribed feed again and store the new stories
+    Refresh {
+        /// Also fetch feeds that are not due yet or ask to be skipped now
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Print the subscribed feeds as OPML, for other feed readers to import
+    Export {
+        /// Write to this file instead
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+
+    /// Remove old stories according to the retention settings
+    Prune {
+        /// Only report what would be removed
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 impl Cli {
@@ -65,33 +100,111 @@ impl Cli {
     }
 
     pub fn handle_args(self) -> crate::error::Result<()> {
-        if let Some(Commands::Read {
-            feed,
-            story,
-            story_all,
-            url,
-            verbose,
-        }) = self.command
-        {
-            let fetched_feed = fetch_feed(url.as_str())?;
-            if feed {
-                println!("{fetched_feed:?}");
-            } else if story_all {
-                let stories = fetched_feed.stories();
-                println!("{stories:?}");
-            } else {
-                let story = story.unwrap_or_default();
-                let story = fetched_feed.stories().get(story).unwrap();
-                println!("{story:?}");
+        let config = Config::load(self.config.as_deref())?;
+
+        match self.command {
+            Some(Commands::Read {
+                feed,
+                story,
+                story_all,
+                url,
+                verbose: _,
+            }) => {
+                let fetched_feed = fetch_feed(url.as_str())?;
+                if feed {
+                    println!("{fetched_feed:?}");
+                } else if story_all {
+                    let stories = fetched_feed.stories();
+                    println!("{stories:?}");
+                } else {
+                    let story = story.unwrap_or_default();
+                    let story = fetched_feed.stories().get(story).unwrap();
+                    println!("{story:?}");
+                }
+                Ok(())
+            }
+
+            Some(Commands::Refresh { force }) => {
+                let db = Self::connect(&self.database)?;
+                let downloader = Downloader::new(&db, config.podcasts.library_dir());
+                let now = Utc::now();
+                let feeds = find_many_feed(None, &db)?;
+                let mut duplicates = Duplicates::new(&feeds);
+                for mut feed in feeds {
+                    if let Some(reason) = feed.not_due(now).filter(|_| !force) {
+                        println!("{}: skipped, {reason}", feed.title());
+                        continue;
+                    }
+                    match refresh_feed(
+                        &mut feed,
+                        fetch_feed_retrying,
+                        &config.retention,
+                        &duplicates,
+                        &db,
+                    ) {
+                        Ok(report) => {
+                            duplicates.add(&feed);
+                            downloader.auto_download(&feed);
+                            if let Err(error) =
+                                notify_new_stories(&feed, &report, &config.notifications)
+                            {
+                                eprintln!("{}: {error}", feed.title());
+                            }
+                            println!(
+                                "{}: {} new ({} already in other feeds), {} pruned",
+                                feed.title(),
+                                report.added,
+                                report.duplicates,
+                                report.pruned
+                            );
+                        }
+                        // One broken feed should not stop the others from refreshing
+                        Err(error) => eprintln!("{}: {error}", feed.title()),
+                    }
+                }
+                downloader.wait();
+                Ok(())
+            }
+
+            Some(Commands::Export { output }) => {
+                let db = Self::connect(&self.database)?;
+                let opml = export_opml(&find_many_feed(None, &db)?);
+                match output {
+                    Some(path) => fs::write(path, opml)?,
+                    None => print!("{opml}"),
+                }
+                Ok(())
+            }
+
+            Some(Commands::Prune { dry_run }) => {
+                let db = Self::connect(&self.database)?;
+                let now = Utc::now();
+                for mut feed in find_many_feed(None, &db)? {
+                    let pruned = feed.prune(&config.retention, now);
+                    if pruned > 0 && !dry_run {
+                        replace_one_feed(&feed, &db)?;
+                    }
+                    let verb = if dry_run { "would remove" } else { "removed" };
+                    println!("{}: {verb} {pruned} stories", feed.title());
+                }
+                Ok(())
+            }
+
+            None => {
+                let db = Self::connect(&self.database)?;
+                let downloader = Downloader::new(&db, config.podcasts.library_dir());
+                let undo = UndoStack::new(&db);
+                let notifier = Notifier::new();
+                let mut app = crate::App::new(&db, &config, &downloader, &undo, &notifier);
+
+                app.load()?;
+                app.run()
             }
-            Ok(())
-        } else {
-            let client = Client::with_uri_str(self.database)?;
-            let db = client.database("main");
-            let mut app = crate::App::new(&db);
-
-            app.load()?;
-            app.run()
         }
     }
+
+    fn connect(database: &str) -> crate::error::Result<Database> {
+        let client = Client::with_uri_str(database)?;
+        Ok(client.database("main"))
+    }
 }
@@ -0,0 +1,299 @@
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::error::Error;
+
+/// Settings read from `config.toml`, every field falls back to a default
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct Config {
+    pub retention: Retention,
+    pub podcasts: Podcasts,
+    pub reading: Reading,
+    pub browser: Browser,
+    pub interface: Interface,
+    pub notifications: Notifications,
+}
+
+/// Which stories are allowed to be pruned from a feed
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Retention {
+    /// Stories kept per feed, the oldest ones go first
+    pub max_stories: Option<usize>,
+    /// Stories published longer ago than this are removed
+    pub max_age_days: Option<i64>,
+    /// Never remove stories that were not read yet
+    pub keep_unread: bool,
+    /// Never remove starred stories
+    pub keep_starred: bool,
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Retention {
+            max_stories: Some(500),
+            max_age_days: None,
+            keep_unread: false,
+            keep_starred: true,
+        }
+    }
+}
+
+/// How podcast episodes are handled
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Podcasts {
+    /// Command the episode URL is handed to, arguments are split on whitespace
+    pub player: String,
+    /// Where downloaded episodes are stored, one directory per feed
+    pub library: Option<PathBuf>,
+}
+
+impl Default for Podcasts {
+    fn default() -> Self {
+        Podcasts {
+            player: String::from("mpv --no-video"),
+            library: None,
+        }
+    }
+}
+
+impl Podcasts {
+    /// The configured library, or `$XDG_DATA_HOME/rss-rs/episodes`
+    pub fn library_dir(&self) -> PathBuf {
+        if let Some(library) = &self.library {
+            return library.clone();
+        }
+
+        data_dir().join("episodes")
+    }
+}
+
+/// How stories are presented for reading
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Reading {
+    /// Reading speed the estimated reading times are based on
+    pub words_per_minute: usize,
+    /// Show the reading time of every story in the stories list
+    pub reading_time_column: bool,
+}
+
+impl Default for Reading {
+    fn default() -> Self {
+        Reading {
+            words_per_minute: 230,
+            reading_time_column: true,
+        }
+    }
+}
+
+/// How the terminal interface behaves
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Interface {
+    /// Capture the mouse for clicking and scrolling. Without it the terminal's own
+    /// text selection works.
+    pub mouse: bool,
+    /// One menu at a time, or feeds, stories and the story side by side
+    pub layout: LayoutMode,
+    /// How the feeds list is sorted at start, it can be changed while running
+    pub feed_order: FeedOrder,
+    /// The colors at start, they can be switched while running
+    pub theme: Theme,
+}
+
+impl Default for Interface {
+    fn default() -> Self {
+        Interface {
+            mouse: true,
+            layout: LayoutMode::default(),
+            feed_order: FeedOrder::default(),
+            theme: Theme::default(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutMode {
+    #[default]
+    Menus,
+    Panes,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    /// The colors of the One Dark editor theme
+    #[default]
+    OneDark,
+    /// The terminal's own colors, which follow its light or dark scheme
+    Terminal,
+}
+
+impl Theme {
+    pub fn next(self) -> Theme {
+        match self {
+            Theme::OneDark => Theme::Terminal,
+            Theme::Terminal => Theme::OneDark,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Theme::OneDark => "One Dark",
+            Theme::Terminal => "Terminal Colors",
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedOrder {
+    /// The order feeds were moved into
+    #[default]
+    Manual,
+    Alphabetical,
+    /// Most unread stories first
+    Unread,
+    /// Newest story first
+    Updated,
+}
+
+impl FeedOrder {
+    pub fn next(self) -> FeedOrder {
+        match self {
+            FeedOrder::Manual => FeedOrder::Alphabetical,
+            FeedOrder::Alphabetical => FeedOrder::Unread,
+            FeedOrder::Unread => FeedOrder::Updated,
+            FeedOrder::Updated => FeedOrder::Manual,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FeedOrder::Manual => "Your Order",
+            FeedOrder::Alphabetical => "A to Z",
+            FeedOrder::Unread => "Most Unread",
+            FeedOrder::Updated => "Recently Updated",
+        }
+    }
+}
+
+/// How feeds that asked for it tell about new stories
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Notifications {
+    /// Command the message is handed to, arguments are split on whitespace
+    pub command: String,
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Notifications {
+            command: String::from("notify-send"),
+        }
+    }
+}
+
+/// Browsers and image viewers that draw in the terminal, they get it handed over
+const TERMINAL_PROGRAMS: &[&str] = &[
+    "lynx", "w3m", "links", "links2", "elinks", "browsh", "carbonyl", "chawan", "cha", "chafa",
+    "timg", "viu", "catimg",
+];
+
+/// The program links are opened with
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct Browser {
+    /// Command the link is handed to, arguments are split on whitespace
+    pub command: Option<String>,
+    /// Command images are opened with instead, like an image viewer
+    pub images: Option<String>,
+    /// Whether the commands run in the terminal, guessed from their names when unset
+    pub terminal: Option<bool>,
+}
+
+impl Browser {
+    /// The configured command, or the first one in `$BROWSER`, or the desktop's opener
+    pub fn command(&self) -> String {
+        if let Some(command) = &self.command {
+            return command.clone();
+        }
+
+        let from_env = env::var("BROWSER").ok().and_then(|browser| {
+            let first = browser.split(':').next()?.trim();
+            (!first.is_empty()).then(|| first.to_string())
+        });
+        from_env.unwrap_or_else(|| {
+            String::from(if cfg!(target_os = "macos") {
+                "open"
+            } else {
+                "xdg-open"
+            })
+        })
+    }
+
+    /// The configured image viewer, or the command links are opened with
+    pub fn image_command(&self) -> String {
+        self.images.clone().unwrap_or_else(|| self.command())
+    }
+
+    /// Whether `command_line` needs the terminal to show anything
+    pub fn in_terminal(&self, command_line: &str) -> bool {
+        self.terminal.unwrap_or_else(|| {
+            let program = command_line.split_whitespace().next().unwrap_or_default();
+            let name = Path::new(program)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            TERMINAL_PROGRAMS.contains(&name)
+        })
+    }
+}
+
+/// `$XDG_DATA_HOME/rss-rs`, or `~/.local/share/rss-rs`
+pub fn data_dir() -> PathBuf {
+    let data_home = match env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME").unwrap_or_default()).join(".local/share"),
+    };
+    data_home.join("rss-rs")
+}
+
+impl Config {
+    /// Reads the config from `path`, or from the default location when there is none.
+    /// A missing file is not an error, the defaults are used instead.
+    pub fn load(path: Option<&Path>) -> crate::error::Result<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match Self::default_path() {
+                Some(path) => path,
+                None => return Ok(Config::default()),
+            },
+        };
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Config::default()),
+            Err(error) => return Err(error.into()),
+        };
+
+        toml::from_str(&text).map_err(|source| Error::Config { path, source })
+    }
+
+    /// `$XDG_CONFIG_HOME/rss-rs/config.toml`, or `~/.config/rss-rs/config.toml`
+    pub fn default_path() -> Option<PathBuf> {
+        let config_home = match env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        };
+        Some(config_home.join("rss-rs").join("config.toml"))
+    }
+}
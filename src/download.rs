@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use mongodb::bson::doc;
+use mongodb::bson::oid::ObjectId;
+use mongodb::sync::Database;
+
+use reqwest::header::RANGE;
+use reqwest::StatusCode;
+
+use url::Url;
+
+use crate::error::Error;
+use crate::models::{update_one_feed, Feed, Story};
+
+#[derive(Debug, Clone)]
+pub enum DownloadState {
+    Running { received: u64, total: Option<u64> },
+    Finished(PathBuf),
+    Failed(String),
+}
+
+impl DownloadState {
+    /// Percentage done, when the server told us how big the file is
+    pub fn percent(&self) -> Option<u64> {
+        match self {
+            DownloadState::Running {
+                received,
+                total: Some(total),
+            } if *total > 0 => Some(received * 100 / total),
+            _ => None,
+        }
+    }
+}
+
+/// Downloads episodes in the background, one thread per episode
+pub struct Downloader {
+    db: Database,
+    library: PathBuf,
+
+    states: Arc<Mutex<HashMap<ObjectId, DownloadState>>>,
+    /// Why downloads failed, until the status bar took it
+    failures: Arc<Mutex<Vec<String>>>,
+    handles: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl Downloader {
+    pub fn new(db: &Database, library: impl Into<PathBuf>) -> Self {
+        Downloader {
+            db: db.clone(),
+            library: library.into(),
+            states: Arc::new(Mutex::new(HashMap::new())),
+            failures: Arc::new(Mutex::new(Vec::new())),
+            handles: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn state(&self, story: &Story) -> Option<DownloadState> {
+        self.states.lock().unwrap().get(&story.id).cloned()
+    }
+
+    /// What went wrong with the downloads that failed since the last call
+    pub fn take_failures(&self) -> Vec<String> {
+        self.failures.lock().unwrap().drain(..).collect()
+    }
+
+    /// The downloaded episode of a story, if it is still on disk
+    pub fn local_file(&self, story: &Story) -> Option<PathBuf> {
+        let path = match self.state(story) {
+            Some(DownloadState::Finished(path)) => path,
+            _ => PathBuf::from(story.download()?),
+        };
+        path.is_file().then_some(path)
+    }
+
+    /// Starts downloading the episode of a story, unless it is already there or on its way
+    pub fn download(&self, feed: &Feed, story: &Story) {
+        let enclosure = match story.enclosure() {
+            Some(enclosure) => enclosure,
+            None => return,
+        };
+        if self.local_file(story).is_some() {
+            return;
+        }
+
+        let mut states = self.states.lock().unwrap();
+        if let Some(DownloadState::Running { .. }) = states.get(&story.id) {
+            return;
+        }
+        states.insert(
+            story.id,
+            DownloadState::Running {
+                received: 0,
+                total: enclosure.length(),
+            },
+        );
+        drop(states);
+
+        let url = enclosure.url().to_string();
+        let path = self
+            .library
+            .join(file_name(feed.title()))
+            .join(episode_file_name(story, &url));
+        let states = Arc::clone(&self.states);
+        let failures = Arc::clone(&self.failures);
+        let db = self.db.clone();
+        let id = story.id;
+        let title = story.title().unwrap_or("episode").to_string();
+
+        let handle = thread::spawn(move || {
+            let progress = |received, total| {
+                states
+                    .lock()
+                    .unwrap()
+                    .insert(id, DownloadState::Running { received, total });
+            };
+
+            let state = match fetch_to_file(&url, &path, progress)
+                .and_then(|_| mark_downloaded(id, &path, &db))
+            {
+                Ok(()) => DownloadState::Finished(path),
+                Err(error) => {
+                    let error = error.to_string();
+                    failures
+                        .lock()
+                        .unwrap()
+                        .push(format!("{error}, while downloading {title}"));
+                    DownloadState::Failed(error)
+                }
+            };
+            states.lock().unwrap().insert(id, state);
+        });
+        self.handles.lock().unwrap().push(handle);
+    }
+
+    /// Downloads the newest episodes of a feed, as many as the feed asks for
+    pub fn auto_download(&self, feed: &Feed) {
+        feed.stories()
+            .iter()
+            .filter(|story| story.is_podcast())
+            .take(feed.auto_download())
+            .for_each(|story| self.download(feed, story));
+    }
+
+    /// Blocks until every download started so far is done
+    pub fn wait(&self) {
+        let handles: Vec<JoinHandle<()>> = self.handles.lock().unwrap().drain(..).collect();
+        for handle in handles {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Downloads `url` into `path`, picking up where an earlier attempt left off
+fn fetch_to_file(
+    url: &str,
+    path: &Path,
+    mut progress: impl FnMut(u64, Option<u64>),
+) -> crate::error::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut part = path.as_os_str().to_owned();
+    part.push(".part");
+    let part = PathBuf::from(part);
+    let resume_from = fs::metadata(&part).map(|meta| meta.len()).unwrap_or(0);
+
+    let mut request = reqwest::blocking::Client::builder()
+        // Episodes can take a long while, the default timeout is meant for feeds
+        .timeout(None)
+        .build()
+        .map_err(|error| Error::fetch(url, error))?
+        .get(url);
+    if resume_from > 0 {
+        request = request.header(RANGE, format!("bytes={resume_from}-"));
+    }
+    let response = request.send().map_err(|error| Error::fetch(url, error))?;
+
+    // Everything we asked for is already there
+    if response.status() == StatusCode::RANGE_NOT_SATISFIABLE && resume_from > 0 {
+        fs::rename(&part, path)?;
+        return Ok(());
+    }
+    let mut response = response
+        .error_for_status()
+        .map_err(|error| Error::fetch(url, error))?;
+
+    // Servers that ignore the range send the whole file again
+    let (mut file, mut received) = if response.status() == StatusCode::PARTIAL_CONTENT {
+        (OpenOptions::new().append(true).open(&part)?, resume_from)
+    } else {
+        (File::create(&part)?, 0)
+    };
+    let total = response.content_length().map(|length| length + received);
+
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = response.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        file.write_all(&buffer[..read])?;
+        received += read as u64;
+        progress(received, total);
+    }
+    file.flush()?;
+
+    fs::rename(&part, path)?;
+    Ok(())
+}
+
+fn mark_downloaded(id: ObjectId, path: &Path, db: &Database) -> crate::error::Result<()> {
+    update_one_feed(
+        doc! {"stories._id": id},
+        doc! {"$set": {"stories.$.download": path.to_string_lossy().as_ref()}},
+        db,
+    )?;
+    Ok(())
+}
+
+/// The title names the file, the story id tells apart episodes with the same title
+fn episode_file_name(story: &Story, url: &str) -> String {
+    let title = story.title().unwrap_or("episode");
+    let extension = Url::parse(url)
+        .ok()
+        .and_then(|url| {
+            let segment = url.path_segments()?.next_back()?.to_string();
+            let extension = Path::new(&segment).extension()?.to_str()?.to_string();
+            Some(extension)
+        })
+        .or_else(|| {
+            let mime_type = story.enclosure()?.mime_type();
+            let extension = match mime_type {
+                "audio/mpeg" => "mp3",
+                "audio/mp4" | "audio/x-m4a" => "m4a",
+                "audio/ogg" => "ogg",
+                "video/mp4" => "mp4",
+                _ => return None,
+            };
+            Some(extension.to_string())
+        })
+        .unwrap_or_else(|| String::from("bin"));
+
+    format!(
+        "{} {}.{}",
+        file_name(title),
+        story.id.to_hex(),
+        file_name(&extension)
+    )
+}
+
+/// Something safe to use as a single path component
+fn file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || " -_".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(100)
+        .collect();
+
+    match name.trim() {
+        "" => String::from("untitled"),
+        name => name.to_string(),
+    }
+}
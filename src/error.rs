@@ -1,15 +1,195 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::process::ExitStatus;
+
 use thiserror::Error;
 
+use mongodb::error::{ErrorKind, RETRYABLE_WRITE_ERROR};
+
 #[derive(Error, Debug)]
 pub enum Error {
-    #[error("ERROR: could not fetch data")]
-    Reqwest(#[from] reqwest::Error),
+    #[error("ERROR: could not fetch {url}: {kind}")]
+    Fetch {
+        url: String,
+        kind: FetchKind,
+        #[source]
+        source: reqwest::Error,
+    },
 
-    #[error("ERROR: could not parse rss document")]
-    Rss(#[from] rss::Error),
+    #[error("ERROR: could not parse rss document from {url} at {position}: {source}")]
+    Rss {
+        url: String,
+        position: Position,
+        #[source]
+        source: rss::Error,
+    },
 
     #[error("ERROR: could not interact with Mongo Db")]
     MongoDb(#[from] mongodb::error::Error),
+
+    #[error("ERROR: input/output failure: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("ERROR: feed no longer exists in the database")]
+    FeedNotFound,
+
+    #[error("ERROR: no command configured for this action")]
+    EmptyCommand,
+
+    #[error("ERROR: {program} {status}")]
+    CommandFailed { program: String, status: ExitStatus },
+
+    #[error("ERROR: could not find an article at {url}")]
+    NoArticle { url: String },
+
+    #[error("ERROR: already subscribed to this feed as {title}")]
+    AlreadySubscribed { title: String },
+
+    #[error("ERROR: could not read config file {}: {source}", path.display())]
+    Config {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+}
+
+impl Error {
+    /// Wraps a failed request together with the URL it was made for
+    pub fn fetch(url: impl Into<String>, source: reqwest::Error) -> Self {
+        Error::Fetch {
+            url: url.into(),
+            kind: FetchKind::from(&source),
+            source,
+        }
+    }
+
+    /// Whether trying the same thing again later has a chance of succeeding
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Fetch { kind, .. } => kind.is_retryable(),
+            Error::Rss { .. } => false,
+            Error::MongoDb(error) => {
+                error.contains_label(RETRYABLE_WRITE_ERROR)
+                    || matches!(
+                        *error.kind,
+                        ErrorKind::Io(_)
+                            | ErrorKind::DnsResolve { .. }
+                            | ErrorKind::ServerSelection { .. }
+                            | ErrorKind::ConnectionPoolCleared { .. }
+                    )
+            }
+            Error::Io(_)
+            | Error::FeedNotFound
+            | Error::EmptyCommand
+            | Error::CommandFailed { .. }
+            | Error::NoArticle { .. }
+            | Error::AlreadySubscribed { .. }
+            | Error::Config { .. } => false,
+        }
+    }
+}
+
+/// What went wrong while talking to a feed's server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchKind {
+    Timeout,
+    Dns,
+    Connect,
+    Tls,
+    Status(u16),
+    Body,
+    Other,
+}
+
+impl FetchKind {
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            FetchKind::Timeout | FetchKind::Connect => true,
+            // Too many requests, and gateways or servers that are busy for a moment
+            FetchKind::Status(status) => matches!(status, 429 | 502 | 503 | 504),
+            // An unknown host, a bad certificate or a body that can not be decoded
+            // are still there a moment later
+            FetchKind::Dns | FetchKind::Tls | FetchKind::Body | FetchKind::Other => false,
+        }
+    }
+}
+
+impl From<&reqwest::Error> for FetchKind {
+    fn from(error: &reqwest::Error) -> Self {
+        if let Some(status) = error.status() {
+            return FetchKind::Status(status.as_u16());
+        }
+        if error.is_timeout() {
+            return FetchKind::Timeout;
+        }
+        if error.is_body() || error.is_decode() {
+            return FetchKind::Body;
+        }
+
+        // reqwest does not tell DNS and TLS failures apart, so look at the causes
+        let mut causes = String::new();
+        let mut source = std::error::Error::source(error);
+        while let Some(cause) = source {
+            causes.push_str(&cause.to_string().to_lowercase());
+            causes.push(' ');
+            source = cause.source();
+        }
+
+        if causes.contains("dns error") || causes.contains("failed to lookup address") {
+            FetchKind::Dns
+        } else if causes.contains("certificate")
+            || causes.contains("ssl")
+            || causes.contains("tls")
+            || causes.contains("handshake")
+        {
+            FetchKind::Tls
+        } else if error.is_connect() {
+            FetchKind::Connect
+        } else {
+            FetchKind::Other
+        }
+    }
+}
+
+impl fmt::Display for FetchKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchKind::Timeout => write!(f, "request timed out"),
+            FetchKind::Dns => write!(f, "could not resolve host"),
+            FetchKind::Connect => write!(f, "could not connect"),
+            FetchKind::Tls => write!(f, "secure connection failed"),
+            FetchKind::Status(status) => write!(f, "server answered with HTTP {status}"),
+            FetchKind::Body => write!(f, "could not read response"),
+            FetchKind::Other => write!(f, "request failed"),
+        }
+    }
+}
+
+/// Line and column (both starting at 1) inside a fetched document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    /// Finds the line and column of a byte offset into `text`
+    pub fn from_offset(text: &str, offset: usize) -> Self {
+        let offset = offset.min(text.len());
+        let before = &text.as_bytes()[..offset];
+        let line = before.iter().filter(|b| **b == b'\n').count() + 1;
+        let column = match before.iter().rposition(|b| *b == b'\n') {
+            Some(newline) => offset - newline,
+            None => offset + 1,
+        };
+        Position { line, column }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -1,8 +1,14 @@
 mod app;
 mod cli;
+mod config;
+mod download;
 mod error;
 mod menus;
 mod models;
+mod notifier;
+mod readability;
+mod render;
+mod undo;
 mod util;
 
 pub use app::App;
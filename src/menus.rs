@@ -1,41 +1,112 @@
+mod category_popup;
+mod command_palette;
 mod contents_menu;
 mod delete_feed_popup;
+mod edit_feed_popup;
+mod error_popup;
+mod feed_info_popup;
 mod feeds_menu;
+mod links_popup;
+mod panes_menu;
+mod status_bar;
 mod stories_menu;
 mod subscribe_popup;
 
+pub use category_popup::CategoryPopup;
+pub use command_palette::{opens_palette, Choice, CommandPalette};
 pub use contents_menu::ContentsMenu;
 pub use delete_feed_popup::DeleteFeedPopup;
+pub use edit_feed_popup::EditFeedPopup;
+pub use error_popup::ErrorPopup;
+pub use feed_info_popup::FeedInfoPopup;
 pub use feeds_menu::FeedsMenu;
+pub use links_popup::LinksPopup;
+pub use panes_menu::PanesMenu;
+pub use status_bar::StatusBar;
 pub use stories_menu::StoriesMenu;
 pub use subscribe_popup::SubscribePopup;
 
 use std::io::Stdout;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::style::Style;
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Clear, Paragraph};
 use tui::Frame;
 use tui::{backend::CrosstermBackend, style::Color};
 
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 
+use crate::config::{Config, Theme};
+use crate::download::Downloader;
 use crate::models::{Feed, Story};
+use crate::notifier::Notifier;
+use crate::util::{run_in_terminal, spawn_detached};
 
 pub enum MenuState {
     Feeds,
-    Stories(Option<Feed>),
-    Contents(Option<Story>),
+    Stories(Option<Box<Feed>>),
+    Contents(Option<Box<Story>>),
+    /// Feeds, stories and the story at once, see `PanesMenu`
+    Panes,
     Exit,
 }
 
+impl MenuState {
+    /// The same menu without the payload used for entering it
+    pub fn settled(&self) -> MenuState {
+        match self {
+            MenuState::Feeds => MenuState::Feeds,
+            MenuState::Stories(_) => MenuState::Stories(None),
+            MenuState::Contents(_) => MenuState::Contents(None),
+            MenuState::Panes => MenuState::Panes,
+            MenuState::Exit => MenuState::Exit,
+        }
+    }
+}
+
 pub trait Menu {
     fn draw(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>);
-    fn transition(&mut self, key_event: KeyEvent) -> MenuState;
-    fn observer(&mut self);
+    fn transition(&mut self, key_event: KeyEvent) -> crate::error::Result<MenuState>;
+    fn observer(&mut self) -> crate::error::Result<()>;
     fn reload(&mut self) -> crate::error::Result<()>;
     fn state(&mut self) -> MenuState;
+
+    /// Clicks and the scroll wheel, menus without anything to click ignore them
+    fn mouse(&mut self, _mouse_event: MouseEvent) -> crate::error::Result<MenuState> {
+        Ok(self.state())
+    }
+}
+
+/// Whether the terminal's own colors are used instead of One Dark's
+static TERMINAL_COLORS: AtomicBool = AtomicBool::new(false);
+
+pub fn set_theme(theme: Theme) {
+    TERMINAL_COLORS.store(theme == Theme::Terminal, Ordering::Relaxed);
+}
+
+pub fn theme() -> Theme {
+    if TERMINAL_COLORS.load(Ordering::Relaxed) {
+        Theme::Terminal
+    } else {
+        Theme::OneDark
+    }
 }
 
+/// Moves on to the next theme, the status bar tells which one
+pub fn switch_theme(notifier: &Notifier) {
+    let theme = theme().next();
+    set_theme(theme);
+    notifier.tell(format!("Theme: {}", theme.label()));
+}
+
+/// The color of the theme for one of the terminal's named colors
 pub fn one_dark(color: Color) -> Color {
+    if theme() == Theme::Terminal {
+        return color;
+    }
     match color {
         Color::Black => Color::Rgb(40, 44, 52),
         Color::Red => Color::Rgb(224, 108, 117),
@@ -76,3 +147,342 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         )
         .split(popup_layout[1])[1]
 }
+
+/// Something a menu does, the key bound to it and what pressing the key runs. Actions
+/// without a single key of their own, like moving up and down, are only listed.
+pub struct Action<M> {
+    pub keys: &'static str,
+    pub description: &'static str,
+    pub key: Option<KeyCode>,
+    pub run: Option<fn(&mut M) -> crate::error::Result<MenuState>>,
+}
+
+/// What the action bound to `code` runs, if any
+pub fn action_for<M>(
+    actions: &[Action<M>],
+    code: KeyCode,
+) -> Option<fn(&mut M) -> crate::error::Result<MenuState>> {
+    actions
+        .iter()
+        .find(|action| action.key == Some(code))
+        .and_then(|action| action.run)
+}
+
+/// Terminals smaller than this show the commands on demand instead of in a box
+const COMPACT_WIDTH: u16 = 100;
+const COMPACT_HEIGHT: u16 = 32;
+
+/// Where the parts of a menu go on a screen of a given size
+pub struct MenuLayout {
+    pub commands: Option<Rect>,
+    pub main: Rect,
+    pub footer: Option<Rect>,
+}
+
+impl MenuLayout {
+    pub fn new(area: Rect) -> Self {
+        if area.width < COMPACT_WIDTH || area.height < COMPACT_HEIGHT {
+            // Leave the bottom row to the status bar
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(1),
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                ])
+                .split(area);
+            return MenuLayout {
+                commands: None,
+                main: chunks[0],
+                footer: Some(chunks[1]),
+            };
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(5)
+            .constraints([Constraint::Percentage(25), Constraint::Percentage(80)])
+            .split(area);
+        MenuLayout {
+            commands: Some(chunks[0]),
+            main: chunks[1],
+            footer: None,
+        }
+    }
+
+    /// Whether an area is too narrow for every column
+    pub fn is_narrow(area: Rect) -> bool {
+        area.width < COMPACT_WIDTH
+    }
+}
+
+/// Every action of a menu as a line of its keys and what they do, with the keys lined
+/// up in a column. Both the commands box and the help are built from this.
+pub fn commands<M>(actions: &[Action<M>]) -> Vec<Spans<'static>> {
+    let width = actions
+        .iter()
+        .map(|action| action.keys.chars().count())
+        .max()
+        .unwrap_or(0)
+        + 2;
+    actions
+        .iter()
+        .map(|action| {
+            Spans::from(vec![
+                Span::styled(
+                    format!("{:width$}", action.keys),
+                    Style::default().fg(one_dark(Color::Green)),
+                ),
+                Span::raw(action.description),
+            ])
+        })
+        .collect()
+}
+
+/// Lines side by side in as many columns as it takes to fit them in `area`
+fn draw_columns(f: &mut Frame<CrosstermBackend<Stdout>>, area: Rect, lines: Vec<Spans>) {
+    if area.height == 0 || lines.is_empty() {
+        return;
+    }
+    let rows = area.height as usize;
+    let count = lines.len().div_ceil(rows);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, count as u32); count])
+        .split(area);
+
+    for (column, lines) in columns.iter().zip(lines.chunks(rows)) {
+        f.render_widget(Paragraph::new(lines.to_vec()), *column);
+    }
+}
+
+/// The commands box, or the footer pointing to them on small screens
+pub fn draw_commands<M>(
+    f: &mut Frame<CrosstermBackend<Stdout>>,
+    layout: &MenuLayout,
+    actions: &[Action<M>],
+) {
+    if let Some(area) = layout.commands {
+        let block = Block::default().title("Commands").borders(Borders::ALL);
+        f.render_widget(block, area);
+
+        let help_chunks = Layout::default()
+            .constraints(vec![Constraint::Percentage(100)])
+            .margin(2)
+            .split(area);
+        draw_columns(f, help_chunks[0], commands(actions));
+    }
+
+    if let Some(area) = layout.footer {
+        draw_footer(f, area, vec![("?", "Commands"), ("ESC", "Quit")]);
+    }
+}
+
+/// A single line of keys and what they do
+pub fn draw_footer(f: &mut Frame<CrosstermBackend<Stdout>>, area: Rect, keys: Vec<(&str, &str)>) {
+    let spans: Vec<Span> = keys
+        .into_iter()
+        .flat_map(|(key, description)| {
+            [
+                Span::styled(
+                    format!("{key} "),
+                    Style::default().fg(one_dark(Color::Green)),
+                ),
+                Span::raw(format!("{description}  ")),
+            ]
+        })
+        .collect();
+    let footer =
+        Paragraph::new(Spans::from(spans)).style(Style::default().fg(one_dark(Color::Gray)));
+    f.render_widget(footer, area);
+}
+
+/// Every command of a menu, over the menu
+pub fn draw_help<M>(f: &mut Frame<CrosstermBackend<Stdout>>, actions: &[Action<M>]) {
+    let popup_area = centered_rect(80, 80, f.size());
+    let block = Block::default()
+        .title("Commands")
+        .borders(Borders::ALL)
+        .style(Style::default().bg(one_dark(Color::DarkGray)));
+    f.render_widget(Clear, popup_area);
+    f.render_widget(block, popup_area);
+
+    let help_chunks = Layout::default()
+        .constraints(vec![Constraint::Percentage(100)])
+        .margin(1)
+        .horizontal_margin(2)
+        .split(popup_area);
+    draw_columns(f, help_chunks[0], commands(actions));
+}
+
+/// Borders of the pane with the focus stand out from the others
+pub fn border_style(focused: bool) -> Style {
+    if focused {
+        Style::default()
+    } else {
+        Style::default().fg(one_dark(Color::Gray))
+    }
+}
+
+/// The first item a list or table shows. Their states keep it private, so it is worked
+/// out the same way tui does for items one row high.
+pub fn list_offset(offset: usize, selected: Option<usize>, rows: u16) -> usize {
+    let rows = rows as usize;
+    match selected {
+        Some(selected) if rows > 0 && selected >= offset + rows => selected + 1 - rows,
+        Some(selected) => offset.min(selected),
+        None => 0,
+    }
+}
+
+/// Index of the item under the mouse in a list drawn in `area` from `offset` onwards
+pub fn item_at(area: Rect, offset: usize, column: u16, row: u16) -> Option<usize> {
+    let inside = column >= area.x
+        && column < area.x + area.width
+        && row >= area.y
+        && row < area.y + area.height;
+    inside.then(|| offset + (row - area.y) as usize)
+}
+
+/// Index of the tab under the mouse, tabs are laid out like ` Back │ Subscribe `
+pub fn tab_at(titles: &[&str], area: Rect, column: u16, row: u16) -> Option<usize> {
+    if row != area.y {
+        return None;
+    }
+    let mut x = area.x;
+    for (index, title) in titles.iter().enumerate() {
+        let width = title.chars().count() as u16 + 2;
+        if column >= x && column < x + width {
+            return Some(index);
+        }
+        // One more for the divider
+        x += width + 1;
+    }
+    None
+}
+
+/// Tells a double click on an item apart from two single ones
+#[derive(Default)]
+pub struct Clicks {
+    last: Option<(usize, Instant)>,
+}
+
+impl Clicks {
+    pub fn double(&mut self, index: usize) -> bool {
+        let now = Instant::now();
+        let double = matches!(self.last, Some((last, at))
+            if last == index && now.duration_since(at) < Duration::from_millis(400));
+        // A third click starts over
+        self.last = if double { None } else { Some((index, now)) };
+        double
+    }
+}
+
+/// Opens a link with the configured browser. A browser that runs in the terminal gets
+/// it until it quits, others are not waited for.
+pub fn open_in_browser(
+    url: &str,
+    config: &Config,
+    notifier: &Notifier,
+) -> crate::error::Result<()> {
+    open_with(&config.browser.command(), url, config, notifier)
+}
+
+/// Opens an image of a story in the image viewer, or in the browser when there is none
+pub fn open_image(url: &str, config: &Config, notifier: &Notifier) -> crate::error::Result<()> {
+    open_with(&config.browser.image_command(), url, config, notifier)
+}
+
+fn open_with(
+    command_line: &str,
+    url: &str,
+    config: &Config,
+    notifier: &Notifier,
+) -> crate::error::Result<()> {
+    if url.is_empty() {
+        return Ok(());
+    }
+    if config.browser.in_terminal(command_line) {
+        notifier.lent_terminal();
+        run_in_terminal(command_line, url, config.interface.mouse)
+    } else {
+        spawn_detached(command_line, url)
+    }
+}
+
+/// The title and link of a story, one per line, to paste elsewhere
+pub fn title_and_link(story: &Story) -> String {
+    let link = story.link().unwrap_or_default();
+    match story.title() {
+        Some(title) => format!("{title}\n{link}"),
+        None => link.to_string(),
+    }
+}
+
+/// Hands the terminal and the episode attached to a story over to the configured
+/// player until it quits, so playing can be paused and stopped from there. The
+/// downloaded file is preferred so playing works offline.
+pub fn play_episode(
+    story: &Story,
+    config: &Config,
+    downloader: &Downloader,
+    notifier: &Notifier,
+) -> crate::error::Result<()> {
+    let episode = match (downloader.local_file(story), story.enclosure()) {
+        (Some(path), _) => path.to_string_lossy().into_owned(),
+        (None, Some(enclosure)) => enclosure.url().to_string(),
+        (None, None) => return Ok(()),
+    };
+    notifier.lent_terminal();
+    run_in_terminal(&config.podcasts.player, &episode, config.interface.mouse)
+}
+
+/// Formats seconds like `1:02:03` or `42:10`
+pub fn format_duration(secs: u64) -> String {
+    let (hours, minutes, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{secs:02}")
+    } else {
+        format!("{minutes}:{secs:02}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// How the help writes a key, in front of whatever else does the same
+    fn label(code: KeyCode) -> String {
+        match code {
+            KeyCode::Char(c) if c.is_ascii_uppercase() => format!("⇧{c}"),
+            KeyCode::Char(c) => c.to_uppercase().to_string(),
+            KeyCode::Enter => String::from("ENTER"),
+            KeyCode::Esc => String::from("ESC"),
+            KeyCode::Left => String::from("←"),
+            KeyCode::PageUp => String::from("PGUP"),
+            KeyCode::PageDown => String::from("PGDN"),
+            KeyCode::Home => String::from("HOME"),
+            KeyCode::End => String::from("END"),
+            code => format!("{code:?}"),
+        }
+    }
+
+    /// Every key of the help runs something, no key is bound twice and the help shows
+    /// the key that is bound
+    pub(super) fn assert_actions_match_keys<M>(actions: &[Action<M>]) {
+        let mut codes = Vec::new();
+        for action in actions {
+            assert_eq!(
+                action.key.is_some(),
+                action.run.is_some(),
+                "{} runs something only if it has a key",
+                action.description
+            );
+            if let Some(code) = action.key {
+                assert!(!codes.contains(&code), "{code:?} is bound twice");
+                codes.push(code);
+                assert_eq!(action.keys.split(' ').next(), Some(label(code).as_str()));
+            }
+        }
+    }
+}
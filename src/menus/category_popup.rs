@@ -0,0 +1,196 @@
+use tui::backend::CrosstermBackend;
+use tui::layout::Rect;
+use tui::style::{Color, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Clear, List, ListItem, ListState};
+use tui::Frame;
+
+use std::io::Stdout;
+
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+
+use super::{centered_rect, item_at, list_offset, one_dark, Clicks, Menu, MenuState};
+
+/// The categories of a feed's stories to pick one to filter them by, every story is
+/// the first choice
+pub struct CategoryPopup<'a> {
+    title: &'a str,
+    /// Categories and how many stories are in each, `None` for all stories
+    categories: Vec<(Option<String>, usize)>,
+    state: ListState,
+    /// Where the list was drawn and the first category it showed, for the mouse
+    list_area: Rect,
+    offset: usize,
+    clicks: Clicks,
+
+    pub popped: bool,
+    chosen: Option<Option<String>>,
+}
+
+impl<'a> CategoryPopup<'a> {
+    pub fn new() -> Self {
+        CategoryPopup {
+            title: "Filter Stories by Category",
+            categories: vec![],
+            state: ListState::default(),
+            list_area: Rect::default(),
+            offset: 0,
+            clicks: Clicks::default(),
+            popped: false,
+            chosen: None,
+        }
+    }
+
+    /// Opens the popup with the selection on the category the stories are filtered by
+    pub fn open(
+        &mut self,
+        categories: Vec<(String, usize)>,
+        stories: usize,
+        current: Option<&str>,
+    ) {
+        self.categories = vec![(None, stories)];
+        self.categories.extend(
+            categories
+                .into_iter()
+                .map(|(category, count)| (Some(category), count)),
+        );
+        let selected = self
+            .categories
+            .iter()
+            .position(|(category, _)| category.as_deref() == current)
+            .unwrap_or(0);
+        self.state.select(Some(selected));
+        self.popped = true;
+    }
+
+    /// The category that was picked, `Some(None)` for all stories
+    pub fn take_chosen(&mut self) -> Option<Option<String>> {
+        self.chosen.take()
+    }
+
+    fn next(&mut self) {
+        let i = match self.state.selected() {
+            Some(i) if i + 1 < self.categories.len() => i + 1,
+            _ => 0,
+        };
+        self.state.select(Some(i));
+    }
+
+    fn previous(&mut self) {
+        let i = match self.state.selected() {
+            Some(0) | None => self.categories.len().saturating_sub(1),
+            Some(i) => i - 1,
+        };
+        self.state.select(Some(i));
+    }
+}
+
+impl<'a> Menu for CategoryPopup<'a> {
+    fn draw(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
+        let popup_area = centered_rect(40, 50, f.size());
+
+        let block = Block::default()
+            .title(self.title)
+            .borders(Borders::ALL)
+            .style(Style::default().bg(one_dark(Color::DarkGray)));
+
+        let categories: Vec<ListItem> = self
+            .categories
+            .iter()
+            .map(|(category, count)| {
+                ListItem::new(Spans::from(vec![
+                    Span::raw(category.as_deref().unwrap_or("All Stories")),
+                    Span::styled(
+                        format!(" ({count})"),
+                        Style::default().fg(one_dark(Color::Gray)),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(categories)
+            .block(block)
+            .style(Style::default().fg(one_dark(Color::White)))
+            .highlight_style(
+                Style::default()
+                    .bg(one_dark(Color::LightBlue))
+                    .fg(one_dark(Color::Black)),
+            );
+        // Inside the borders
+        self.list_area = Rect::new(
+            popup_area.x + 1,
+            popup_area.y + 1,
+            popup_area.width.saturating_sub(2),
+            popup_area.height.saturating_sub(2),
+        );
+        self.offset = list_offset(self.offset, self.state.selected(), self.list_area.height);
+        f.render_widget(Clear, popup_area);
+        f.render_stateful_widget(list, popup_area, &mut self.state);
+    }
+
+    fn transition(&mut self, key_event: KeyEvent) -> crate::error::Result<MenuState> {
+        match key_event.code {
+            KeyCode::Esc => {
+                self.reload()?;
+            }
+
+            KeyCode::Up => {
+                self.previous();
+            }
+
+            KeyCode::Down => {
+                self.next();
+            }
+
+            KeyCode::Enter => {
+                self.chosen = self
+                    .state
+                    .selected()
+                    .and_then(|i| self.categories.get(i))
+                    .map(|(category, _)| category.clone());
+                self.reload()?;
+            }
+
+            _ => {}
+        }
+        // Fallback if none of the keys were pressed
+        Ok(self.state())
+    }
+
+    fn mouse(&mut self, mouse_event: MouseEvent) -> crate::error::Result<MenuState> {
+        match mouse_event.kind {
+            MouseEventKind::ScrollDown => self.next(),
+            MouseEventKind::ScrollUp => self.previous(),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let clicked = item_at(
+                    self.list_area,
+                    self.offset,
+                    mouse_event.column,
+                    mouse_event.row,
+                )
+                .filter(|i| *i < self.categories.len());
+                if let Some(i) = clicked {
+                    self.state.select(Some(i));
+                    if self.clicks.double(i) {
+                        return self.transition(KeyEvent::from(KeyCode::Enter));
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(self.state())
+    }
+
+    fn reload(&mut self) -> crate::error::Result<()> {
+        self.popped = false;
+        Ok(())
+    }
+
+    fn state(&mut self) -> MenuState {
+        MenuState::Stories(None)
+    }
+
+    fn observer(&mut self) -> crate::error::Result<()> {
+        Ok(())
+    }
+}
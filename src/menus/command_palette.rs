@@ -0,0 +1,262 @@
+use tui::backend::CrosstermBackend;
+use tui::layout::{Constraint, Layout};
+use tui::style::{Color, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+use tui::Frame;
+
+use std::io::Stdout;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use mongodb::bson::oid::ObjectId;
+
+use crate::models::Feed;
+
+use super::{centered_rect, one_dark, Action, Menu, MenuState};
+
+/// What picking an entry of the palette does
+#[derive(Clone, Copy)]
+pub enum Choice {
+    /// The same as pressing the key
+    Key(KeyCode),
+    /// Opens the stories of a feed
+    Feed(ObjectId),
+}
+
+struct Entry {
+    label: String,
+    keys: String,
+    choice: Choice,
+}
+
+/// Every action of a menu in a list that narrows down while typing, so the ones
+/// without a key worth remembering are easy to get to
+pub struct CommandPalette<'a> {
+    title: &'a str,
+    entries: Vec<Entry>,
+    /// Entries matching the input, best first
+    matches: Vec<usize>,
+    state: ListState,
+    input: String,
+
+    pub popped: bool,
+    chosen: Option<Choice>,
+}
+
+impl<'a> CommandPalette<'a> {
+    pub fn new() -> Self {
+        CommandPalette {
+            title: "Command Palette",
+            entries: vec![],
+            matches: vec![],
+            state: ListState::default(),
+            input: String::new(),
+            popped: false,
+            chosen: None,
+        }
+    }
+
+    /// Opens the palette with the actions of a menu that have a key, and the feeds
+    /// that can be jumped to
+    pub fn open<M>(&mut self, actions: &[Action<M>], feeds: &[Feed]) {
+        let actions = actions.iter().filter_map(|action| {
+            Some(Entry {
+                label: action.description.to_string(),
+                keys: action.keys.trim().to_string(),
+                choice: Choice::Key(action.key?),
+            })
+        });
+        let feeds = feeds.iter().map(|feed| Entry {
+            label: format!("Go to Feed: {}", feed.title()),
+            keys: String::new(),
+            choice: Choice::Feed(feed.id),
+        });
+
+        self.entries = actions.chain(feeds).collect();
+        self.input.clear();
+        self.filter();
+        self.popped = true;
+    }
+
+    /// What was picked, if anything
+    pub fn take_chosen(&mut self) -> Option<Choice> {
+        self.chosen.take()
+    }
+
+    fn filter(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| Some((fuzzy_score(&self.input, &entry.label)?, i)))
+            .collect();
+        // Stable, so equally good entries keep their order
+        scored.sort_by_key(|(score, _)| -score);
+        self.matches = scored.into_iter().map(|(_, i)| i).collect();
+        self.state.select(if self.matches.is_empty() {
+            None
+        } else {
+            Some(0)
+        });
+    }
+
+    fn next(&mut self) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) if i + 1 < self.matches.len() => i + 1,
+            _ => 0,
+        };
+        self.state.select(Some(i));
+    }
+
+    fn previous(&mut self) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(0) | None => self.matches.len() - 1,
+            Some(i) => i - 1,
+        };
+        self.state.select(Some(i));
+    }
+}
+
+/// Whether a key opens the palette, `:` or Ctrl-P
+pub fn opens_palette(key_event: &KeyEvent) -> bool {
+    match key_event.code {
+        KeyCode::Char(':') => true,
+        KeyCode::Char('p') => key_event.modifiers.contains(KeyModifiers::CONTROL),
+        _ => false,
+    }
+}
+
+/// How well `query` matches `text`, when its characters appear in `text` in the same
+/// order. Runs of characters and matches at the start of words count more.
+fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous = None;
+
+    for c in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = position + text[position..].iter().position(|t| *t == c)?;
+        score += 1;
+        if found > 0 && previous == Some(found - 1) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
+
+impl<'a> Menu for CommandPalette<'a> {
+    fn draw(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
+        let popup_area = centered_rect(50, 50, f.size());
+
+        let block = Block::default()
+            .title(self.title)
+            .borders(Borders::ALL)
+            .style(Style::default().bg(one_dark(Color::DarkGray)));
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .constraints(vec![Constraint::Length(3), Constraint::Min(1)])
+            .margin(1)
+            .horizontal_margin(2)
+            .split(popup_area);
+
+        let input = Paragraph::new(Spans::from(vec![
+            Span::styled(": ", Style::default().fg(one_dark(Color::Gray))),
+            Span::raw(self.input.as_str()),
+        ]))
+        .style(Style::default().fg(one_dark(Color::Green)))
+        .block(Block::default().borders(Borders::ALL));
+        f.render_widget(input, chunks[0]);
+
+        let items: Vec<ListItem> = self
+            .matches
+            .iter()
+            .map(|i| {
+                let entry = &self.entries[*i];
+                let mut spans = vec![Span::raw(entry.label.as_str())];
+                if !entry.keys.is_empty() {
+                    spans.push(Span::styled(
+                        format!("  {}", entry.keys),
+                        Style::default().fg(one_dark(Color::Green)),
+                    ));
+                }
+                ListItem::new(Spans::from(spans))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .style(Style::default().fg(one_dark(Color::White)))
+            .highlight_style(
+                Style::default()
+                    .bg(one_dark(Color::LightBlue))
+                    .fg(one_dark(Color::Black)),
+            );
+        f.render_stateful_widget(list, chunks[1], &mut self.state);
+    }
+
+    fn transition(&mut self, key_event: KeyEvent) -> crate::error::Result<MenuState> {
+        match key_event.code {
+            KeyCode::Esc => {
+                self.reload()?;
+            }
+
+            KeyCode::Up => {
+                self.previous();
+            }
+
+            KeyCode::Down => {
+                self.next();
+            }
+
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                self.filter();
+            }
+
+            KeyCode::Backspace => {
+                self.input.pop();
+                self.filter();
+            }
+
+            KeyCode::Enter => {
+                self.chosen = self
+                    .state
+                    .selected()
+                    .and_then(|i| self.matches.get(i))
+                    .map(|i| self.entries[*i].choice);
+                self.reload()?;
+            }
+
+            _ => {}
+        }
+        // Fallback if none of the keys were pressed
+        Ok(self.state())
+    }
+
+    fn reload(&mut self) -> crate::error::Result<()> {
+        self.popped = false;
+        self.input.clear();
+        Ok(())
+    }
+
+    fn state(&mut self) -> MenuState {
+        MenuState::Feeds
+    }
+
+    fn observer(&mut self) -> crate::error::Result<()> {
+        Ok(())
+    }
+}
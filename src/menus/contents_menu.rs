@@ -1,30 +1,287 @@
 use tui::backend::CrosstermBackend;
-use tui::layout::{Constraint, Direction, Layout};
+use tui::layout::{Constraint, Direction, Layout, Rect};
 use tui::style::{Color, Modifier, Style};
 use tui::text::{Span, Spans};
 use tui::widgets::{Block, Borders, Paragraph, Wrap};
 use tui::Frame;
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent, MouseEventKind};
 
 use std::io::Stdout;
-use std::process::Command;
 
-use crate::models::Story;
-
-use super::{one_dark, Menu, MenuState};
-
-pub struct ContentsMenu {
+use mongodb::bson::doc;
+use mongodb::sync::Database;
+
+use crate::config::Config;
+use crate::download::Downloader;
+use crate::models::{
+    find_many_feed, find_one_feed, update_story_content, ContentFormat, Feed, Story,
+};
+use crate::notifier::Notifier;
+use crate::render::{render_html, render_text, Rendered};
+use crate::undo::UndoStack;
+use crate::util::{copy_to_clipboard, fetch_feed, fetch_full_text, subscribe};
+
+use super::{
+    action_for, border_style, draw_commands, draw_help, format_duration, one_dark, open_in_browser,
+    opens_palette, play_episode, switch_theme, title_and_link, Action, Choice, CommandPalette,
+    LinksPopup, Menu, MenuLayout, MenuState,
+};
+
+pub struct ContentsMenu<'a> {
     story: Story,
+    /// The feed the story is from, to move on to the stories next to it
+    feed: Feed,
+    /// Words in the story, counted when it is opened
+    words: usize,
 
     scroll: usize,
+    /// Rows of the contents area and of the rendered story, as last drawn
+    viewport: usize,
+    height: usize,
+    /// Rendered content and the width it was rendered for
+    rendered: Option<(u16, Rendered)>,
+    /// Number of the link typed so far
+    link_number: String,
+    /// The help with every command is open
+    help: bool,
+
+    links_popup: LinksPopup<'a>,
+    command_palette: CommandPalette<'a>,
+
+    db: &'a Database,
+    config: &'a Config,
+    downloader: &'a Downloader,
+    undo: &'a UndoStack,
+    notifier: &'a Notifier,
 }
 
-impl ContentsMenu {
-    pub fn new() -> Self {
+impl<'a> ContentsMenu<'a> {
+    const ACTIONS: &'a [Action<Self>] = &[
+        Action {
+            keys: "←",
+            description: "Go Back",
+            key: Some(KeyCode::Left),
+            run: Some(|_| Ok(MenuState::Stories(None))),
+        },
+        Action {
+            keys: "ENTER",
+            description: "Open in Browser",
+            key: Some(KeyCode::Enter),
+            run: Some(|menu| {
+                menu.open_link()?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "0-9 ENTER",
+            description: "Open the Link With That Number",
+            key: None,
+            run: None,
+        },
+        Action {
+            keys: "P",
+            description: "Play Episode",
+            key: Some(KeyCode::Char('p')),
+            run: Some(|menu| {
+                play_episode(&menu.story, menu.config, menu.downloader, menu.notifier)?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "L",
+            description: "Pick a Link to Open, Copy or Subscribe to",
+            key: Some(KeyCode::Char('l')),
+            run: Some(|menu| {
+                menu.pick_link();
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "I",
+            description: "Pick an Image to Open or Copy",
+            key: Some(KeyCode::Char('i')),
+            run: Some(|menu| {
+                menu.pick_image();
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "F",
+            description: "Fetch the Full Article",
+            key: Some(KeyCode::Char('f')),
+            run: Some(|menu| {
+                fetch_full_text(&mut menu.story)?;
+                update_story_content(&menu.story, menu.db)?;
+                menu.rendered = None;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "Y",
+            description: "Copy Link",
+            key: Some(KeyCode::Char('y')),
+            run: Some(|menu| {
+                copy_to_clipboard(menu.story.link().unwrap_or_default())?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "⇧Y",
+            description: "Copy Title and Link",
+            key: Some(KeyCode::Char('Y')),
+            run: Some(|menu| {
+                copy_to_clipboard(&title_and_link(&menu.story))?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "U",
+            description: "Undo Deleting a Feed or Marking Stories Read",
+            key: Some(KeyCode::Char('u')),
+            run: Some(|menu| {
+                menu.undo.undo()?;
+                // Read states changed under the copy of the feed
+                if let Some(feed) = find_one_feed(Some(doc! {"_id": menu.feed.id}), menu.db)? {
+                    menu.feed = feed;
+                }
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "[",
+            description: "Previous Story",
+            key: Some(KeyCode::Char('[')),
+            run: Some(|menu| {
+                menu.previous()?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "]",
+            description: "Next Story",
+            key: Some(KeyCode::Char(']')),
+            run: Some(|menu| {
+                menu.next()?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "N",
+            description: "Next Unread Story in This Feed",
+            key: Some(KeyCode::Char('n')),
+            run: Some(|menu| {
+                menu.next_unread(false)?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "⇧N",
+            description: "Next Unread Story in Any Feed",
+            key: Some(KeyCode::Char('N')),
+            run: Some(|menu| {
+                menu.next_unread(true)?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "↑ ↓",
+            description: "Scroll Up and Down",
+            key: None,
+            run: None,
+        },
+        Action {
+            keys: "PGUP",
+            description: "Page Up",
+            key: Some(KeyCode::PageUp),
+            run: Some(|menu| {
+                menu.scroll_by(-menu.page());
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "PGDN SPACE",
+            description: "Page Down",
+            key: Some(KeyCode::PageDown),
+            run: Some(|menu| {
+                menu.scroll_by(menu.page());
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "HOME",
+            description: "Go to the Top",
+            key: Some(KeyCode::Home),
+            run: Some(|menu| {
+                menu.scroll = 0;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "END",
+            description: "Go to the End",
+            key: Some(KeyCode::End),
+            run: Some(|menu| {
+                menu.scroll = menu.max_scroll();
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "⇧T",
+            description: "Switch Theme",
+            key: Some(KeyCode::Char('T')),
+            run: Some(|menu| {
+                switch_theme(menu.notifier);
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: ": ^P",
+            description: "Command Palette",
+            key: None,
+            run: None,
+        },
+        Action {
+            keys: "?",
+            description: "Show All Commands",
+            key: Some(KeyCode::Char('?')),
+            run: Some(|menu| {
+                menu.help = true;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "ESC",
+            description: "Quit",
+            key: Some(KeyCode::Esc),
+            run: Some(|_| Ok(MenuState::Exit)),
+        },
+    ];
+
+    pub fn new(
+        db: &'a Database,
+        config: &'a Config,
+        downloader: &'a Downloader,
+        undo: &'a UndoStack,
+        notifier: &'a Notifier,
+    ) -> Self {
         ContentsMenu {
             story: Story::default(),
+            feed: Feed::default(),
+            words: 0,
             scroll: 0,
+            viewport: 0,
+            height: 0,
+            rendered: None,
+            link_number: String::new(),
+            help: false,
+            links_popup: LinksPopup::new(config, notifier),
+            command_palette: CommandPalette::new(),
+            db,
+            config,
+            downloader,
+            undo,
+            notifier,
         }
     }
 
@@ -33,73 +290,203 @@ impl ContentsMenu {
     }
 
     pub fn set_story(&mut self, story: impl Into<Story>) {
-        self.story = story.into()
+        self.story = story.into();
+        self.words = self.story.word_count();
+        self.scroll = 0;
+        self.rendered = None;
+        self.link_number.clear();
     }
-}
 
-impl Menu for ContentsMenu {
-    fn draw(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
-        let background = Block::default().style(Style::default().bg(one_dark(Color::Black)));
-        f.render_widget(background, f.size());
+    fn max_scroll(&self) -> usize {
+        self.height.saturating_sub(self.viewport)
+    }
 
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(5)
-            .constraints(vec![Constraint::Percentage(25), Constraint::Percentage(80)])
-            .split(f.size());
+    /// Rows to scroll by a page, keeping one row of the page before in sight
+    fn page(&self) -> isize {
+        self.viewport.max(2) as isize - 1
+    }
 
-        // COMMANDS BOX
-        let block = Block::default().title("Commands").borders(Borders::ALL);
+    fn scroll_by(&mut self, rows: isize) {
+        self.scroll = self
+            .scroll
+            .saturating_add_signed(rows)
+            .min(self.max_scroll());
+    }
 
-        f.render_widget(block, chunks[0]);
+    /// Opens the link with the number typed before, or the story itself
+    fn open_link(&mut self) -> crate::error::Result<()> {
+        let number = self.link_number.parse::<usize>().ok();
+        self.link_number.clear();
+
+        match number {
+            Some(number) => {
+                let link = self
+                    .rendered
+                    .as_ref()
+                    .and_then(|(_, rendered)| rendered.links.get(number.checked_sub(1)?).cloned());
+                if let Some(link) = link {
+                    open_in_browser(&link, self.config, self.notifier)?;
+                }
+            }
+            None => open_in_browser(
+                self.story.link().unwrap_or_default(),
+                self.config,
+                self.notifier,
+            )?,
+        }
+        Ok(())
+    }
 
-        let help_chunks = Layout::default()
-            .constraints(vec![Constraint::Percentage(100)])
-            .margin(2)
-            .split(chunks[0]);
+    fn pick_link(&mut self) {
+        let mut links = match &self.rendered {
+            Some((_, rendered)) => rendered.links.clone(),
+            None => vec![],
+        };
+        // After the numbered links, so their numbers stay the same
+        links.extend(self.story.comments().map(String::from));
+        self.links_popup.set_links(links);
+        self.links_popup.popped = true;
+    }
 
-        let back_spans = Spans::from(vec![
-            Span::styled("←     ", Style::default().fg(one_dark(Color::Green))),
-            Span::raw("Go Back"),
-        ]);
+    fn pick_image(&mut self) {
+        let images = match &self.rendered {
+            Some((_, rendered)) => rendered.images.clone(),
+            None => self.story.images().to_vec(),
+        };
+        self.links_popup.set_images(&images);
+        self.links_popup.popped = true;
+    }
 
-        let enter_spans = Spans::from(vec![
-            Span::styled("ENTER ", Style::default().fg(one_dark(Color::Green))),
-            Span::raw("Open in Browser"),
-        ]);
+    /// How far down the story is, for the title
+    fn position_label(&self) -> String {
+        match self.max_scroll() {
+            0 => String::from("All"),
+            _ if self.scroll == 0 => String::from("Top"),
+            max if self.scroll >= max => String::from("Bot"),
+            max => format!("{}%", self.scroll * 100 / max),
+        }
+    }
 
-        let arrows_spans = Spans::from(vec![
-            Span::styled("↑ ↓   ", Style::default().fg(one_dark(Color::Green))),
-            Span::raw("Navigate Up and Down"),
-        ]);
+    pub fn feed(&self) -> &Feed {
+        &self.feed
+    }
 
-        let quit_spans = Spans::from(vec![
-            Span::styled("ESC   ", Style::default().fg(one_dark(Color::Green))),
-            Span::raw("Quit"),
-        ]);
+    pub fn set_feed(&mut self, feed: impl Into<Feed>) {
+        self.feed = feed.into();
+    }
 
-        let paragraph = Paragraph::new(vec![
-            back_spans,
-            enter_spans,
-            Spans::from(""),
-            arrows_spans,
-            quit_spans,
-        ])
-        .wrap(Wrap { trim: true });
+    fn position(&self) -> Option<usize> {
+        self.feed
+            .stories()
+            .iter()
+            .position(|story| story.id == self.story.id)
+    }
 
-        f.render_widget(paragraph, help_chunks[0]);
-        // COMMANDS BOX
+    /// Shows another story of the feed and marks it as read
+    fn open(&mut self, index: usize) -> crate::error::Result<()> {
+        if let Some(story) = self.feed.stories_mut().get_mut(index) {
+            self.undo.mark_story_read(story)?;
+            story.read = true;
+            let story = story.clone();
+            self.set_story(story);
+        }
+        Ok(())
+    }
 
-        // CONTENTS
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .title(self.story.title().unwrap());
-        f.render_widget(block, chunks[1]);
+    fn next(&mut self) -> crate::error::Result<()> {
+        match self.position() {
+            Some(i) if i + 1 < self.feed.stories().len() => self.open(i + 1),
+            _ => Ok(()),
+        }
+    }
+
+    fn previous(&mut self) -> crate::error::Result<()> {
+        match self.position() {
+            Some(i) if i > 0 => self.open(i - 1),
+            _ => Ok(()),
+        }
+    }
 
+    /// Opens the next unread story after this one. With `across_feeds` the search goes
+    /// on through the other feeds, and comes back around to the top of this one.
+    fn next_unread(&mut self, across_feeds: bool) -> crate::error::Result<()> {
+        let start = self.position().map_or(0, |i| i + 1);
+        let unread_after = |feed: &Feed, start: usize| {
+            feed.stories()
+                .iter()
+                .skip(start)
+                .position(|story| !story.read)
+                .map(|i| i + start)
+        };
+
+        if let Some(i) = unread_after(&self.feed, start) {
+            return self.open(i);
+        }
+        if !across_feeds {
+            return Ok(());
+        }
+
+        let feeds = find_many_feed(None, self.db)?;
+        let current = feeds.iter().position(|feed| feed.id == self.feed.id);
+        let order = match current {
+            Some(current) => (current + 1..feeds.len()).chain(0..=current).collect(),
+            None => (0..feeds.len()).collect::<Vec<usize>>(),
+        };
+        for index in order {
+            if let Some(i) = unread_after(&feeds[index], 0) {
+                self.feed = feeds[index].clone();
+                return self.open(i);
+            }
+        }
+        Ok(())
+    }
+
+    /// Categories of the story, and where it came from and is discussed
+    fn tags(&self) -> Spans<'static> {
+        let mut spans: Vec<Span> = self
+            .story
+            .categories()
+            .iter()
+            .map(|category| {
+                Span::styled(
+                    format!("#{category} "),
+                    Style::default().fg(one_dark(Color::LightBlue)),
+                )
+            })
+            .collect();
+        if let Some(source) = self.story.source() {
+            spans.push(Span::styled(
+                format!("via {} ", source.name()),
+                Style::default().fg(one_dark(Color::Gray)),
+            ));
+        }
+        if self.story.comments().is_some() {
+            spans.push(Span::styled(
+                "· Comments in L",
+                Style::default().fg(one_dark(Color::Gray)),
+            ));
+        }
+        Spans::from(spans)
+    }
+
+    /// The story alone, in `area`. Panes that are not focused get a dimmer border.
+    pub fn draw_main(
+        &mut self,
+        f: &mut Frame<CrosstermBackend<Stdout>>,
+        area: Rect,
+        focused: bool,
+    ) {
+        // CONTENTS
+        let tags = self.tags();
+        let tags_height = if tags.0.is_empty() { 0 } else { 1 };
         let contents_chunks = Layout::default()
-            .constraints(vec![Constraint::Percentage(10), Constraint::Percentage(90)])
+            .constraints(vec![
+                Constraint::Length(3),
+                Constraint::Length(tags_height),
+                Constraint::Min(1),
+            ])
             .margin(1)
-            .split(chunks[1]);
+            .split(area);
 
         // META BOX
         let meta_chunks = Layout::default()
@@ -123,6 +510,52 @@ impl Menu for ContentsMenu {
 
         f.render_widget(paragraph, meta_chunks[0]);
 
+        if self.story.is_podcast() {
+            let mut episode = vec![];
+            if let Some(season) = self.story.season() {
+                episode.push(format!("Season {season}"));
+            }
+            if let Some(number) = self.story.episode() {
+                episode.push(format!("Episode {number}"));
+            }
+            if let Some(secs) = self.story.duration_secs() {
+                episode.push(format_duration(secs));
+            }
+            if let Some(enclosure) = self.story.enclosure() {
+                episode.push(enclosure.mime_type().to_string());
+            }
+
+            let episode_spans = Spans::from(vec![
+                Span::styled("♫ ", Style::default().fg(one_dark(Color::Magenta))),
+                Span::styled(
+                    episode.join(" · "),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+            ]);
+
+            let paragraph = Paragraph::new(episode_spans).wrap(Wrap { trim: true });
+
+            f.render_widget(paragraph, meta_chunks[1]);
+        } else if self.words > 0 {
+            let minutes = self
+                .words
+                .div_ceil(self.config.reading.words_per_minute.max(1));
+            let reading_spans = Spans::from(vec![
+                Span::styled(
+                    "Reading Time: ",
+                    Style::default().fg(one_dark(Color::Green)),
+                ),
+                Span::styled(
+                    format!("{minutes} min · {} words", self.words),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+            ]);
+
+            let paragraph = Paragraph::new(reading_spans).wrap(Wrap { trim: true });
+
+            f.render_widget(paragraph, meta_chunks[1]);
+        }
+
         let author = self.story.author().unwrap_or_default();
         let creator = self.story.creator().unwrap_or_default();
 
@@ -139,52 +572,155 @@ impl Menu for ContentsMenu {
         f.render_widget(paragraph, meta_chunks[2]);
         // META BOX
 
-        let description = self.story.description().unwrap_or_default();
-        let content = self.story.content().unwrap_or_default();
-
-        let paragraph = Paragraph::new(if content.is_empty() {
-            description
-        } else {
-            content
-        })
-        .wrap(Wrap { trim: true })
-        .scroll((self.scroll as u16, 0));
+        f.render_widget(Paragraph::new(tags), contents_chunks[1]);
+
+        // Only render again when the width changed
+        let width = contents_chunks[2].width;
+        if !matches!(&self.rendered, Some((rendered_width, _)) if *rendered_width == width) {
+            let description = self.story.description().unwrap_or_default();
+            let content = self.story.content().unwrap_or_default();
+            let mut rendered = if content.is_empty() {
+                render_html(description, width as usize, self.story.link())
+            } else if self.story.content_format() == ContentFormat::Html {
+                render_html(content, width as usize, self.story.link())
+            } else {
+                render_text(content)
+            };
+            rendered.list_images(self.story.images());
+            rendered.wrap(width);
+            self.rendered = Some((width, rendered));
+        }
+        let lines = match &self.rendered {
+            Some((_, rendered)) => {
+                self.height = rendered.height();
+                rendered.lines.clone()
+            }
+            None => vec![],
+        };
+        // The area can shrink, keep the end of the story in view
+        self.viewport = contents_chunks[2].height as usize;
+        self.scroll = self.scroll.min(self.max_scroll());
 
-        f.render_widget(paragraph, contents_chunks[1]);
+        // Drawn last so the title knows where in the story we are
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style(focused))
+            .title(if self.link_number.is_empty() {
+                format!(
+                    "{} [{}]",
+                    self.story.title().unwrap_or_default(),
+                    self.position_label()
+                )
+            } else {
+                format!("Open link [{}]", self.link_number)
+            });
+        f.render_widget(block, area);
+
+        let paragraph = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll as u16, 0));
+
+        f.render_widget(paragraph, contents_chunks[2]);
         // CONTENTS
     }
 
-    fn transition(&mut self, key_event: KeyEvent) -> MenuState {
-        match key_event.code {
-            KeyCode::Esc => {
-                return MenuState::Exit;
-            }
+    /// Does what was picked in the command palette
+    fn choose(&mut self, choice: Choice) -> crate::error::Result<MenuState> {
+        match choice {
+            Choice::Key(code) => self.transition(KeyEvent::from(code)),
+            Choice::Feed(id) => match find_one_feed(Some(doc! {"_id": id}), self.db)? {
+                Some(feed) => Ok(MenuState::Stories(Some(Box::new(feed)))),
+                None => Ok(self.state()),
+            },
+        }
+    }
 
-            KeyCode::Up => {
-                if self.scroll > 0 {
-                    self.scroll = self.scroll - 1;
-                }
-            }
+    /// Whether the help or a popup is open
+    pub fn has_overlay(&self) -> bool {
+        self.help || self.links_popup.popped || self.command_palette.popped
+    }
 
-            KeyCode::Down => {
-                self.scroll = self.scroll + 1;
-            }
+    /// The help and popups, over everything else
+    pub fn draw_overlays(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
+        if self.help {
+            draw_help(f, Self::ACTIONS);
+        }
+
+        // POPUP
+        if self.links_popup.popped {
+            self.links_popup.draw(f);
+        } else if self.command_palette.popped {
+            self.command_palette.draw(f);
+        }
+        // POPUP
+    }
+}
 
-            KeyCode::Left => {
-                return MenuState::Stories(None);
+impl<'a> Menu for ContentsMenu<'a> {
+    fn draw(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
+        let background = Block::default().style(Style::default().bg(one_dark(Color::Black)));
+        f.render_widget(background, f.size());
+
+        let layout = MenuLayout::new(f.size());
+        self.draw_main(f, layout.main, true);
+        draw_commands(f, &layout, Self::ACTIONS);
+        self.draw_overlays(f);
+    }
+
+    fn transition(&mut self, key_event: KeyEvent) -> crate::error::Result<MenuState> {
+        if self.links_popup.popped {
+            return self.links_popup.transition(key_event);
+        }
+        if self.command_palette.popped {
+            self.command_palette.transition(key_event)?;
+            return match self.command_palette.take_chosen() {
+                Some(choice) => self.choose(choice),
+                None => Ok(self.state()),
+            };
+        }
+        if self.help {
+            // Any of the keys that usually close something closes the help
+            if let KeyCode::Esc | KeyCode::Enter | KeyCode::Char('?') = key_event.code {
+                self.help = false;
             }
+            return Ok(self.state());
+        }
 
-            KeyCode::Enter => {
-                Command::new("xdg-open")
-                    .arg(self.story.link().unwrap_or_default())
-                    .output()
-                    .unwrap();
+        if opens_palette(&key_event) {
+            let feeds = find_many_feed(None, self.db)?;
+            self.command_palette.open(Self::ACTIONS, &feeds);
+        } else if let Some(run) = action_for(Self::ACTIONS, key_event.code) {
+            return run(self);
+        } else {
+            match key_event.code {
+                KeyCode::Up => self.scroll_by(-1),
+                KeyCode::Down => self.scroll_by(1),
+                KeyCode::Char(' ') => self.scroll_by(self.page()),
+                KeyCode::Char(c) if c.is_ascii_digit() => self.link_number.push(c),
+                KeyCode::Backspace => {
+                    self.link_number.pop();
+                }
+                _ => {}
             }
+        }
+        // Fallback if none of the keys were pressed
+        Ok(self.state())
+    }
+
+    fn mouse(&mut self, mouse_event: MouseEvent) -> crate::error::Result<MenuState> {
+        if self.links_popup.popped {
+            return self.links_popup.mouse(mouse_event);
+        }
+        if self.command_palette.popped {
+            return Ok(self.state());
+        }
 
+        match mouse_event.kind {
+            MouseEventKind::ScrollDown => self.scroll_by(3),
+            MouseEventKind::ScrollUp => self.scroll_by(-3),
             _ => {}
         }
-        // Fallback if none of the keys were pressed
-        self.state()
+        Ok(self.state())
     }
 
     fn reload(&mut self) -> crate::error::Result<()> {
@@ -195,5 +731,23 @@ impl Menu for ContentsMenu {
         MenuState::Contents(None)
     }
 
-    fn observer(&mut self) {}
+    fn observer(&mut self) -> crate::error::Result<()> {
+        if let Some(link) = self.links_popup.take_subscribe() {
+            let feed = fetch_feed(&link)?;
+            subscribe(&feed, self.db)?;
+            self.links_popup
+                .set_message(format!("Subscribed to {}", feed.title()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_help_shows_the_keys_that_run_something() {
+        crate::menus::tests::assert_actions_match_keys(ContentsMenu::ACTIONS);
+    }
 }
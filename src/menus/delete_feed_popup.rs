@@ -1,5 +1,5 @@
 use tui::backend::CrosstermBackend;
-use tui::layout::{Constraint, Layout};
+use tui::layout::{Constraint, Layout, Rect};
 use tui::style::{Color, Modifier, Style};
 use tui::text::Spans;
 use tui::widgets::{Block, Borders, Paragraph, Tabs, Wrap};
@@ -7,14 +7,18 @@ use tui::Frame;
 
 use std::io::Stdout;
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 
-use super::{centered_rect, one_dark, Menu, MenuState};
+use super::{centered_rect, one_dark, tab_at, Menu, MenuState};
+
+const BUTTONS: [&str; 2] = ["Back", "Confirm"];
 
 pub struct DeleteFeedPopup<'a> {
     title: &'a str,
     pub deleted: bool,
     pub popped: bool,
+    /// Where the buttons were drawn, for the mouse
+    buttons: Rect,
     choice: bool,
 }
 
@@ -24,6 +28,7 @@ impl<'a> DeleteFeedPopup<'a> {
             title: "Confirm Deletion",
             deleted: false,
             popped: false,
+            buttons: Rect::default(),
             choice: false,
         }
     }
@@ -52,7 +57,7 @@ impl<'a> Menu for DeleteFeedPopup<'a> {
             Paragraph::new("Are you sure you want to delete the feed?").wrap(Wrap { trim: true });
         f.render_widget(paragraph, popup_chunks[0]);
 
-        let tabs = Tabs::new(vec![Spans::from("Back"), Spans::from("Confirm")])
+        let tabs = Tabs::new(BUTTONS.iter().map(|button| Spans::from(*button)).collect())
             .select(self.choice as usize)
             .style(Style::default().fg(one_dark(Color::Green)))
             .highlight_style(
@@ -61,13 +66,14 @@ impl<'a> Menu for DeleteFeedPopup<'a> {
                     .bg(one_dark(Color::Green))
                     .fg(one_dark(Color::DarkGray)),
             );
+        self.buttons = popup_chunks[1];
         f.render_widget(tabs, popup_chunks[1]);
     }
 
-    fn transition(&mut self, key_event: KeyEvent) -> MenuState {
+    fn transition(&mut self, key_event: KeyEvent) -> crate::error::Result<MenuState> {
         match key_event.code {
             KeyCode::Esc => {
-                self.reload().unwrap();
+                self.reload()?;
             }
 
             KeyCode::Left => {
@@ -80,13 +86,24 @@ impl<'a> Menu for DeleteFeedPopup<'a> {
 
             KeyCode::Enter => {
                 self.deleted = self.choice;
-                self.reload().unwrap();
+                self.reload()?;
             }
 
             _ => {}
         }
         // Fallback if none of the keys were pressed
-        self.state()
+        Ok(self.state())
+    }
+
+    fn mouse(&mut self, mouse_event: MouseEvent) -> crate::error::Result<MenuState> {
+        if let MouseEventKind::Down(MouseButton::Left) = mouse_event.kind {
+            let clicked = tab_at(&BUTTONS, self.buttons, mouse_event.column, mouse_event.row);
+            if let Some(index) = clicked {
+                self.choice = index == 1;
+                return self.transition(KeyEvent::from(KeyCode::Enter));
+            }
+        }
+        Ok(self.state())
     }
 
     fn reload(&mut self) -> crate::error::Result<()> {
@@ -99,5 +116,7 @@ impl<'a> Menu for DeleteFeedPopup<'a> {
         MenuState::Feeds
     }
 
-    fn observer(&mut self) {}
+    fn observer(&mut self) -> crate::error::Result<()> {
+        Ok(())
+    }
 }
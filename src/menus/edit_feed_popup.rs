@@ -0,0 +1,372 @@
+use tui::backend::CrosstermBackend;
+use tui::layout::{Constraint, Layout, Rect};
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Clear, Paragraph, Tabs, Wrap};
+use tui::Frame;
+
+use std::io::Stdout;
+
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+
+use crate::models::Feed;
+use crate::util::{fetch_feed, normalize_url};
+
+use super::{centered_rect, item_at, one_dark, tab_at, Menu, MenuState};
+
+const BUTTONS: [&str; 2] = ["Back", "Save"];
+
+/// The settings of a feed, in the order they are shown
+const TITLE: usize = 0;
+const URL: usize = 1;
+const REFRESH: usize = 2;
+const AUTO_DOWNLOAD: usize = 3;
+const FULL_TEXT: usize = 4;
+const NOTIFY: usize = 5;
+/// The row of buttons comes after the settings
+const SAVE: usize = 6;
+
+const LABELS: [&str; 6] = [
+    "Title",
+    "Feed URL",
+    "Refresh Every (Minutes)",
+    "Auto Download Episodes",
+    "Fetch Full Articles",
+    "Notify About New Stories",
+];
+
+pub struct EditFeedPopup<'a> {
+    title: &'a str,
+    feed: Option<Feed>,
+
+    pub popped: bool,
+    /// Where the settings and the buttons were drawn, for the mouse
+    fields: Rect,
+    buttons: Rect,
+    edited: bool,
+
+    selected: usize,
+    choice: bool,
+    /// What was typed for the settings that are text
+    inputs: [String; 4],
+    full_text: bool,
+    notify: bool,
+    /// The outcome of fetching the feed URL, or what is wrong with the input
+    message: Option<(String, bool)>,
+    /// Normalized URLs and titles of the other feeds, a feed can not become one of them
+    others: Vec<(String, String)>,
+}
+
+impl<'a> EditFeedPopup<'a> {
+    pub fn new() -> Self {
+        EditFeedPopup {
+            title: "Edit Feed",
+            feed: None,
+            popped: false,
+            fields: Rect::default(),
+            buttons: Rect::default(),
+            edited: false,
+            selected: TITLE,
+            choice: false,
+            inputs: Default::default(),
+            full_text: false,
+            notify: false,
+            message: None,
+            others: vec![],
+        }
+    }
+
+    /// Opens the popup with the current settings of `feed`, one of `feeds`
+    pub fn open(&mut self, feed: &Feed, feeds: &[Feed]) {
+        self.others = feeds
+            .iter()
+            .filter(|other| other.id != feed.id)
+            .map(|other| (normalize_url(other.rss_link()), other.title().to_string()))
+            .collect();
+        self.inputs = [
+            feed.custom_title().unwrap_or_default().to_string(),
+            feed.rss_link().to_string(),
+            feed.refresh_minutes()
+                .map(|minutes| minutes.to_string())
+                .unwrap_or_default(),
+            feed.auto_download().to_string(),
+        ];
+        self.full_text = feed.full_text();
+        self.notify = feed.notify();
+        self.feed = Some(feed.clone());
+        self.selected = TITLE;
+        self.choice = false;
+        self.message = None;
+        self.popped = true;
+    }
+
+    /// The feed with its new settings, once they were saved
+    pub fn take_edited(&mut self) -> Option<Feed> {
+        if !self.edited {
+            return None;
+        }
+        self.edited = false;
+        self.feed.take()
+    }
+
+    /// Tries the feed URL, so a typo does not go unnoticed until the next refresh
+    fn test_url(&mut self) -> bool {
+        match fetch_feed(self.inputs[URL].trim()) {
+            Ok(feed) => {
+                let message = format!(
+                    "Found \"{}\" with {} stories",
+                    feed.title(),
+                    feed.stories_len()
+                );
+                self.message = Some((message, true));
+                true
+            }
+            Err(error) => {
+                self.message = Some((error.to_string(), false));
+                false
+            }
+        }
+    }
+
+    /// Puts the settings into the feed, or tells what is wrong with them
+    fn save(&mut self) -> crate::error::Result<()> {
+        let refresh_minutes = match self.inputs[REFRESH].trim() {
+            "" => None,
+            minutes => match minutes.parse::<u32>() {
+                Ok(minutes) if minutes > 0 => Some(minutes),
+                _ => {
+                    self.message = Some(("The refresh interval is not a number".into(), false));
+                    self.selected = REFRESH;
+                    return Ok(());
+                }
+            },
+        };
+        let Ok(auto_download) = self.inputs[AUTO_DOWNLOAD].trim().parse::<usize>() else {
+            self.message = Some(("The episodes to download are not a number".into(), false));
+            self.selected = AUTO_DOWNLOAD;
+            return Ok(());
+        };
+
+        let url = self.inputs[URL].trim().to_string();
+        let url_changed = self
+            .feed
+            .as_ref()
+            .is_some_and(|feed| feed.rss_link() != url);
+        let normalized = normalize_url(&url);
+        let taken = self.others.iter().find(|(link, _)| *link == normalized);
+        if let (true, Some((_, title))) = (url_changed, taken) {
+            self.message = Some((format!("Already subscribed to this feed as {title}"), false));
+            self.selected = URL;
+            return Ok(());
+        }
+        if url_changed && !self.test_url() {
+            self.selected = URL;
+            return Ok(());
+        }
+
+        if let Some(feed) = &mut self.feed {
+            let title = self.inputs[TITLE].trim();
+            feed.set_custom_title((!title.is_empty()).then(|| title.to_string()));
+            feed.set_rss_link(url);
+            feed.set_refresh_minutes(refresh_minutes);
+            feed.set_auto_download(auto_download);
+            feed.set_full_text(self.full_text);
+            feed.set_notify(self.notify);
+            self.edited = true;
+        }
+        self.popped = false;
+        Ok(())
+    }
+
+    fn toggle(&mut self) {
+        match self.selected {
+            FULL_TEXT => self.full_text = !self.full_text,
+            NOTIFY => self.notify = !self.notify,
+            _ => {}
+        }
+    }
+
+    fn value(&self, field: usize) -> Span<'_> {
+        let checkbox = |on: bool| Span::raw(if on { "[x]" } else { "[ ]" });
+        match field {
+            FULL_TEXT => checkbox(self.full_text),
+            NOTIFY => checkbox(self.notify),
+            // An empty title falls back to the one of the feed
+            TITLE if self.inputs[TITLE].is_empty() => Span::styled(
+                self.feed
+                    .as_ref()
+                    .map(|feed| feed.fetched_title())
+                    .unwrap_or_default(),
+                Style::default().fg(one_dark(Color::Gray)),
+            ),
+            // Without an interval of our own, the one the feed asks for is used
+            REFRESH if self.inputs[REFRESH].is_empty() => {
+                let ttl = self.feed.as_ref().and_then(|feed| feed.info().ttl());
+                let hint = match ttl {
+                    Some(ttl) => format!("{ttl}, as the feed asks"),
+                    None => String::from("every time"),
+                };
+                Span::styled(hint, Style::default().fg(one_dark(Color::Gray)))
+            }
+            field => Span::raw(self.inputs[field].as_str()),
+        }
+    }
+}
+
+impl<'a> Menu for EditFeedPopup<'a> {
+    fn draw(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
+        let popup_area = centered_rect(60, 50, f.size());
+
+        let block = Block::default()
+            .title(self.title)
+            .borders(Borders::ALL)
+            .style(Style::default().bg(one_dark(Color::DarkGray)));
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .constraints(vec![
+                Constraint::Length(LABELS.len() as u16),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .margin(1)
+            .horizontal_margin(3)
+            .split(popup_area);
+
+        self.fields = chunks[0];
+        let width = LABELS.iter().map(|label| label.len()).max().unwrap_or(0) + 2;
+        let lines: Vec<Spans> = LABELS
+            .iter()
+            .enumerate()
+            .map(|(field, label)| {
+                let style = if field == self.selected {
+                    Style::default()
+                        .add_modifier(Modifier::BOLD)
+                        .fg(one_dark(Color::Green))
+                } else {
+                    Style::default().fg(one_dark(Color::Gray))
+                };
+                let cursor = if field == self.selected && field < FULL_TEXT {
+                    "▏"
+                } else {
+                    ""
+                };
+                Spans::from(vec![
+                    Span::styled(format!("{label:width$}"), style),
+                    self.value(field),
+                    Span::raw(cursor),
+                ])
+            })
+            .collect();
+        f.render_widget(Paragraph::new(lines), chunks[0]);
+
+        if let Some((message, found)) = &self.message {
+            let color = if *found { Color::Green } else { Color::Red };
+            let message = Paragraph::new(message.as_str())
+                .style(Style::default().fg(one_dark(color)))
+                .wrap(Wrap { trim: true });
+            f.render_widget(message, chunks[1]);
+        }
+
+        let mut tabs = Tabs::new(BUTTONS.iter().map(|button| Spans::from(*button)).collect())
+            .style(Style::default().fg(one_dark(Color::Green)));
+        if self.selected == SAVE {
+            tabs = tabs.select(self.choice as usize).highlight_style(
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .bg(one_dark(Color::Green))
+                    .fg(one_dark(Color::DarkGray)),
+            );
+        } else {
+            tabs = tabs.highlight_style(Style::default());
+        }
+        self.buttons = chunks[2];
+        f.render_widget(tabs, chunks[2]);
+    }
+
+    fn transition(&mut self, key_event: KeyEvent) -> crate::error::Result<MenuState> {
+        match key_event.code {
+            KeyCode::Esc => {
+                self.reload()?;
+            }
+
+            KeyCode::Up | KeyCode::BackTab => {
+                self.selected = self.selected.checked_sub(1).unwrap_or(SAVE);
+            }
+
+            KeyCode::Down | KeyCode::Tab => {
+                self.selected = (self.selected + 1) % (SAVE + 1);
+            }
+
+            KeyCode::Left if self.selected == SAVE => {
+                self.choice = false;
+            }
+
+            KeyCode::Right if self.selected == SAVE => {
+                self.choice = true;
+            }
+
+            KeyCode::Enter => match self.selected {
+                SAVE if self.choice => self.save()?,
+                SAVE => self.reload()?,
+                URL => {
+                    self.test_url();
+                }
+                FULL_TEXT | NOTIFY => self.toggle(),
+                _ => self.selected += 1,
+            },
+
+            KeyCode::Char(' ') if self.selected >= FULL_TEXT => {
+                self.toggle();
+            }
+
+            // The numbers only take digits
+            KeyCode::Char(c)
+                if self.selected <= URL || (self.selected < FULL_TEXT && c.is_ascii_digit()) =>
+            {
+                self.inputs[self.selected].push(c);
+            }
+
+            KeyCode::Backspace if self.selected < FULL_TEXT => {
+                self.inputs[self.selected].pop();
+            }
+
+            _ => {}
+        }
+        // Fallback if none of the keys were pressed
+        Ok(self.state())
+    }
+
+    fn mouse(&mut self, mouse_event: MouseEvent) -> crate::error::Result<MenuState> {
+        if let MouseEventKind::Down(MouseButton::Left) = mouse_event.kind {
+            let (column, row) = (mouse_event.column, mouse_event.row);
+            if let Some(index) = tab_at(&BUTTONS, self.buttons, column, row) {
+                self.selected = SAVE;
+                self.choice = index == 1;
+                return self.transition(KeyEvent::from(KeyCode::Enter));
+            }
+            if let Some(field) = item_at(self.fields, 0, column, row).filter(|i| *i < SAVE) {
+                self.selected = field;
+                self.toggle();
+            }
+        }
+        Ok(self.state())
+    }
+
+    fn reload(&mut self) -> crate::error::Result<()> {
+        self.popped = false;
+        self.feed = None;
+        self.edited = false;
+        self.message = None;
+        Ok(())
+    }
+
+    fn state(&mut self) -> MenuState {
+        MenuState::Feeds
+    }
+
+    fn observer(&mut self) -> crate::error::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use tui::backend::CrosstermBackend;
+use tui::layout::{Constraint, Layout, Rect};
+use tui::style::{Color, Modifier, Style};
+use tui::text::Spans;
+use tui::widgets::{Block, Borders, Clear, Paragraph, Tabs, Wrap};
+use tui::Frame;
+
+use std::io::Stdout;
+
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+
+use crate::error::Error;
+
+use super::{centered_rect, one_dark, tab_at, Menu, MenuState};
+
+const BUTTONS: [&str; 1] = ["Dismiss"];
+
+pub struct ErrorPopup<'a> {
+    title: &'a str,
+    message: String,
+    pub popped: bool,
+    /// Where the buttons were drawn, for the mouse
+    buttons: Rect,
+}
+
+impl<'a> ErrorPopup<'a> {
+    pub fn new() -> Self {
+        ErrorPopup {
+            title: "Something Went Wrong",
+            message: String::new(),
+            popped: false,
+            buttons: Rect::default(),
+        }
+    }
+
+    pub fn show(&mut self, error: &Error) {
+        self.message = error.to_string();
+        self.popped = true;
+    }
+}
+
+impl<'a> Menu for ErrorPopup<'a> {
+    fn draw(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
+        let popup_area = centered_rect(50, 20, f.size());
+
+        let popup_chunks = Layout::default()
+            .constraints(vec![Constraint::Percentage(80), Constraint::Percentage(20)])
+            .margin(2)
+            .split(popup_area);
+
+        let block = Block::default()
+            .title(self.title)
+            .borders(Borders::ALL)
+            .style(
+                Style::default()
+                    .bg(one_dark(Color::DarkGray))
+                    .fg(one_dark(Color::Red)),
+            );
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block, popup_area);
+
+        let paragraph = Paragraph::new(self.message.as_str())
+            .style(Style::default().fg(one_dark(Color::White)))
+            .wrap(Wrap { trim: true });
+        f.render_widget(paragraph, popup_chunks[0]);
+
+        let tabs = Tabs::new(BUTTONS.iter().map(|button| Spans::from(*button)).collect())
+            .select(0)
+            .style(Style::default().fg(one_dark(Color::Green)))
+            .highlight_style(
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .bg(one_dark(Color::Green))
+                    .fg(one_dark(Color::DarkGray)),
+            );
+        self.buttons = popup_chunks[1];
+        f.render_widget(tabs, popup_chunks[1]);
+    }
+
+    fn transition(&mut self, key_event: KeyEvent) -> crate::error::Result<MenuState> {
+        if let KeyCode::Esc | KeyCode::Enter = key_event.code {
+            self.reload()?;
+        }
+        // Fallback if none of the keys were pressed
+        Ok(self.state())
+    }
+
+    fn mouse(&mut self, mouse_event: MouseEvent) -> crate::error::Result<MenuState> {
+        if let MouseEventKind::Down(MouseButton::Left) = mouse_event.kind {
+            let clicked = tab_at(&BUTTONS, self.buttons, mouse_event.column, mouse_event.row);
+            if clicked.is_some() {
+                self.reload()?;
+            }
+        }
+        Ok(self.state())
+    }
+
+    fn reload(&mut self) -> crate::error::Result<()> {
+        self.popped = false;
+        Ok(())
+    }
+
+    fn state(&mut self) -> MenuState {
+        MenuState::Feeds
+    }
+
+    fn observer(&mut self) -> crate::error::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+use tui::backend::CrosstermBackend;
+use tui::layout::{Constraint, Layout};
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use tui::Frame;
+
+use std::io::Stdout;
+
+use chrono::Utc;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::config::Config;
+use crate::models::Feed;
+use crate::notifier::Notifier;
+
+use super::{centered_rect, one_dark, open_image, Menu, MenuState};
+
+/// What a feed tells about itself, and what it asks of refreshes
+pub struct FeedInfoPopup<'a> {
+    title: &'a str,
+    feed: Option<Feed>,
+
+    pub popped: bool,
+
+    config: &'a Config,
+    notifier: &'a Notifier,
+}
+
+impl<'a> FeedInfoPopup<'a> {
+    pub fn new(config: &'a Config, notifier: &'a Notifier) -> Self {
+        FeedInfoPopup {
+            title: "About This Feed",
+            feed: None,
+            popped: false,
+            config,
+            notifier,
+        }
+    }
+
+    pub fn open(&mut self, feed: &Feed) {
+        self.feed = Some(feed.clone());
+        self.popped = true;
+    }
+
+    /// Labels and values, `None` for what the feed does not give
+    fn rows(feed: &Feed) -> Vec<(&'static str, Option<String>)> {
+        let info = feed.info();
+        let skip_hours = info
+            .skip_hours()
+            .iter()
+            .map(|hour| format!("{hour:02}:00"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let refresh_hint = match (info.ttl(), info.skips(Utc::now())) {
+            (Some(ttl), true) => Some(format!("every {ttl} minutes, skipping now")),
+            (Some(ttl), false) => Some(format!("every {ttl} minutes")),
+            (None, true) => Some(String::from("skipping now")),
+            (None, false) => None,
+        };
+        // The date as the feed wrote it is kept when it can not be read
+        let last_built = info
+            .last_built()
+            .map(|date| date.format("%Y-%m-%d %H:%M UTC").to_string())
+            .or(info.last_build_date().map(String::from));
+
+        vec![
+            ("Title", Some(feed.fetched_title().to_string())),
+            ("Website", Some(feed.link().to_string())),
+            ("Feed URL", Some(feed.rss_link().to_string())),
+            ("Description", Some(feed.description().to_string())),
+            ("Language", info.language().map(String::from)),
+            ("Image", info.image().map(|image| image.url().to_string())),
+            ("Last Built", last_built),
+            ("Refresh", refresh_hint),
+            (
+                "Next Refresh",
+                Some(
+                    feed.not_due(Utc::now())
+                        .unwrap_or_else(|| String::from("due now")),
+                ),
+            ),
+            ("Skip Hours (GMT)", Some(skip_hours)),
+            ("Skip Days", Some(info.skip_days().join(", "))),
+            ("Generator", info.generator().map(String::from)),
+            ("Managing Editor", info.managing_editor().map(String::from)),
+        ]
+    }
+}
+
+impl<'a> Menu for FeedInfoPopup<'a> {
+    fn draw(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
+        let popup_area = centered_rect(60, 60, f.size());
+
+        let block = Block::default()
+            .title(self.title)
+            .borders(Borders::ALL)
+            .style(Style::default().bg(one_dark(Color::DarkGray)));
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .constraints(vec![Constraint::Min(1), Constraint::Length(1)])
+            .margin(1)
+            .horizontal_margin(2)
+            .split(popup_area);
+
+        let rows = self.feed.as_ref().map(Self::rows).unwrap_or_default();
+        let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0) + 2;
+        let lines: Vec<Spans> = rows
+            .into_iter()
+            .map(|(label, value)| {
+                let value = match value.filter(|value| !value.trim().is_empty()) {
+                    Some(value) => Span::styled(value, Style::default().fg(one_dark(Color::White))),
+                    None => Span::styled("—", Style::default().fg(one_dark(Color::Gray))),
+                };
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{label:width$}"),
+                        Style::default()
+                            .add_modifier(Modifier::BOLD)
+                            .fg(one_dark(Color::Green)),
+                    ),
+                    value,
+                ])
+            })
+            .collect();
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), chunks[0]);
+
+        let mut help = vec![];
+        if self
+            .feed
+            .as_ref()
+            .is_some_and(|feed| feed.info().image().is_some())
+        {
+            help.push(Span::styled(
+                "I ",
+                Style::default().fg(one_dark(Color::Green)),
+            ));
+            help.push(Span::raw("Open Image  "));
+        }
+        help.push(Span::styled(
+            "ESC ",
+            Style::default().fg(one_dark(Color::Green)),
+        ));
+        help.push(Span::raw("Back"));
+        f.render_widget(Paragraph::new(Spans::from(help)), chunks[1]);
+    }
+
+    fn transition(&mut self, key_event: KeyEvent) -> crate::error::Result<MenuState> {
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Enter => {
+                self.reload()?;
+            }
+
+            KeyCode::Char('i') => {
+                let image = self.feed.as_ref().and_then(|feed| feed.info().image());
+                if let Some(image) = image {
+                    open_image(image.url(), self.config, self.notifier)?;
+                }
+            }
+
+            _ => {}
+        }
+        // Fallback if none of the keys were pressed
+        Ok(self.state())
+    }
+
+    fn reload(&mut self) -> crate::error::Result<()> {
+        self.popped = false;
+        self.feed = None;
+        Ok(())
+    }
+
+    fn state(&mut self) -> MenuState {
+        MenuState::Feeds
+    }
+
+    fn observer(&mut self) -> crate::error::Result<()> {
+        Ok(())
+    }
+}
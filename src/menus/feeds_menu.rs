@@ -1,43 +1,288 @@
 use tui::backend::CrosstermBackend;
-use tui::layout::{Constraint, Direction, Layout};
+use tui::layout::{Constraint, Layout, Rect};
 use tui::style::{Color, Style};
 use tui::terminal::Frame;
 use tui::text::{Span, Spans};
-use tui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use tui::widgets::{Block, Borders, List, ListItem, ListState};
+
+use chrono::Utc;
 
-use mongodb::sync::Database;
 use mongodb::bson::doc;
+use mongodb::sync::Database;
 
+use std::cmp::Reverse;
+use std::fs;
 use std::io::Stdout;
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 
-use crate::models::{delete_one_feed, find_many_feed, insert_one_feed, Feed};
+use crate::config::{data_dir, Config, FeedOrder};
+use crate::download::Downloader;
+use crate::models::{find_many_feed, update_one_feed, Duplicates, Feed};
+use crate::notifier::Notifier;
+use crate::undo::UndoStack;
+use crate::util::{export_opml, fetch_feed, notify_new_stories, refresh_feed, subscribe};
 
-use super::{one_dark, DeleteFeedPopup, Menu, MenuState, SubscribePopup};
+use super::{
+    action_for, border_style, draw_commands, draw_help, item_at, list_offset, one_dark,
+    opens_palette, switch_theme, Action, Choice, Clicks, CommandPalette, DeleteFeedPopup,
+    EditFeedPopup, FeedInfoPopup, Menu, MenuLayout, MenuState, SubscribePopup,
+};
 
 pub struct FeedsMenu<'a> {
     title: &'a str,
     feeds: Vec<Feed>,
     state: ListState,
+    order: FeedOrder,
+    /// The help with every command is open
+    help: bool,
+    /// Minutes it takes to read the unread stories of every feed
+    unread_minutes: Vec<usize>,
+    /// Where the list was drawn and the first feed it showed, for the mouse
+    list_area: Rect,
+    offset: usize,
+    clicks: Clicks,
 
     subscribe_popup: SubscribePopup<'a>,
     delete_feed_popup: DeleteFeedPopup<'a>,
+    edit_feed_popup: EditFeedPopup<'a>,
+    feed_info_popup: FeedInfoPopup<'a>,
+    command_palette: CommandPalette<'a>,
 
     db: &'a Database,
+    config: &'a Config,
+    downloader: &'a Downloader,
+    undo: &'a UndoStack,
+    notifier: &'a Notifier,
 }
 
 impl<'a> FeedsMenu<'a> {
-    pub fn new(db: &'a Database) -> Self {
+    const ACTIONS: &'a [Action<Self>] = &[
+        Action {
+            keys: "S",
+            description: "Subscribe to a Feed",
+            key: Some(KeyCode::Char('s')),
+            run: Some(|menu| {
+                menu.subscribe_popup.popped = true;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "D",
+            description: "Delete Feed",
+            key: Some(KeyCode::Char('d')),
+            run: Some(|menu| {
+                menu.delete_feed_popup.popped = true;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "E",
+            description: "Edit Feed Title, URL and Settings",
+            key: Some(KeyCode::Char('e')),
+            run: Some(|menu| {
+                if let Some(feed) = menu.selected().cloned() {
+                    menu.edit_feed_popup.open(&feed, &menu.feeds);
+                }
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "I",
+            description: "About the Feed",
+            key: Some(KeyCode::Char('i')),
+            run: Some(|menu| {
+                if let Some(feed) = menu.selected().cloned() {
+                    menu.feed_info_popup.open(&feed);
+                }
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "ENTER",
+            description: "Load Stories",
+            key: Some(KeyCode::Enter),
+            run: Some(|menu| match menu.selected() {
+                Some(feed) => Ok(MenuState::Stories(Some(Box::new(feed.clone())))),
+                None => Ok(menu.state()),
+            }),
+        },
+        Action {
+            keys: "⇧R",
+            description: "Refresh Feeds From the Web",
+            key: Some(KeyCode::Char('R')),
+            run: Some(|menu| {
+                menu.refresh_all()?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "⇧G",
+            description: "Get the Selected Feed Now, Even if Not Due",
+            key: Some(KeyCode::Char('G')),
+            run: Some(|menu| {
+                menu.refresh_selected()?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "R",
+            description: "Reload Feeds From the Database",
+            key: Some(KeyCode::Char('r')),
+            run: Some(|menu| {
+                menu.reload()?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "O",
+            description: "Sort Feeds by Your Order, Title, Unread or Updated",
+            key: Some(KeyCode::Char('o')),
+            run: Some(|menu| {
+                menu.set_order(menu.order.next());
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "⇧K",
+            description: "Move Feed Up in Your Order",
+            key: Some(KeyCode::Char('K')),
+            run: Some(|menu| {
+                menu.move_feed(true)?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "⇧J",
+            description: "Move Feed Down in Your Order",
+            key: Some(KeyCode::Char('J')),
+            run: Some(|menu| {
+                menu.move_feed(false)?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "M",
+            description: "Mark Every Story of the Feed Read",
+            key: Some(KeyCode::Char('m')),
+            run: Some(|menu| {
+                menu.mark_read()?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "U",
+            description: "Undo Deleting a Feed or Marking Stories Read",
+            key: Some(KeyCode::Char('u')),
+            run: Some(|menu| {
+                menu.undo.undo()?;
+                menu.reload()?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "+",
+            description: "Auto Download More Episodes",
+            key: Some(KeyCode::Char('+')),
+            run: Some(|menu| {
+                menu.change_auto_download(true)?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "-",
+            description: "Auto Download Fewer Episodes",
+            key: Some(KeyCode::Char('-')),
+            run: Some(|menu| {
+                menu.change_auto_download(false)?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "F",
+            description: "Always Fetch Full Articles On/Off",
+            key: Some(KeyCode::Char('f')),
+            run: Some(|menu| {
+                menu.toggle_full_text()?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "↑ ↓",
+            description: "Navigate Up and Down",
+            key: None,
+            run: None,
+        },
+        Action {
+            keys: "⇧X",
+            description: "Export Feeds as OPML",
+            key: Some(KeyCode::Char('X')),
+            run: Some(|menu| {
+                menu.export()?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "⇧T",
+            description: "Switch Theme",
+            key: Some(KeyCode::Char('T')),
+            run: Some(|menu| {
+                switch_theme(menu.notifier);
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: ": ^P",
+            description: "Command Palette",
+            key: None,
+            run: None,
+        },
+        Action {
+            keys: "?",
+            description: "Show All Commands",
+            key: Some(KeyCode::Char('?')),
+            run: Some(|menu| {
+                menu.help = true;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "ESC",
+            description: "Quit",
+            key: Some(KeyCode::Esc),
+            run: Some(|_| Ok(MenuState::Exit)),
+        },
+    ];
+
+    pub fn new(
+        db: &'a Database,
+        config: &'a Config,
+        downloader: &'a Downloader,
+        undo: &'a UndoStack,
+        notifier: &'a Notifier,
+    ) -> Self {
         Self {
             title: "Your Feeds",
             feeds: vec![],
             state: ListState::default(),
+            order: config.interface.feed_order,
+            help: false,
+            unread_minutes: vec![],
+            list_area: Rect::default(),
+            offset: 0,
+            clicks: Clicks::default(),
 
             subscribe_popup: SubscribePopup::new(),
             delete_feed_popup: DeleteFeedPopup::new(),
+            edit_feed_popup: EditFeedPopup::new(),
+            feed_info_popup: FeedInfoPopup::new(config, notifier),
+            command_palette: CommandPalette::new(),
 
             db,
+            config,
+            downloader,
+            undo,
+            notifier,
         }
     }
 
@@ -46,7 +291,209 @@ impl<'a> FeedsMenu<'a> {
     }
 
     pub fn set_feeds(&mut self, feeds: impl Into<Vec<Feed>>) {
+        let selected = self.selected().map(|feed| feed.id);
         self.feeds = feeds.into();
+        self.sort_feeds();
+        self.count_unread_minutes();
+
+        // The selection stays on the same feed wherever the sort put it
+        if let Some(i) = selected.and_then(|id| self.feeds.iter().position(|f| f.id == id)) {
+            self.state.select(Some(i));
+        }
+    }
+
+    fn sort_feeds(&mut self) {
+        match self.order {
+            FeedOrder::Manual => self
+                .feeds
+                .sort_by_key(|feed| (feed.position().is_none(), feed.position())),
+            FeedOrder::Alphabetical => self
+                .feeds
+                .sort_by_cached_key(|feed| feed.title().to_lowercase()),
+            FeedOrder::Unread => self.feeds.sort_by_key(|feed| Reverse(feed.unread_count())),
+            FeedOrder::Updated => self
+                .feeds
+                .sort_by_key(|feed| Reverse(feed.last_published())),
+        }
+    }
+
+    fn set_order(&mut self, order: FeedOrder) {
+        self.order = order;
+        let feeds = std::mem::take(&mut self.feeds);
+        self.set_feeds(feeds);
+    }
+
+    /// Moves the selected feed one place up or down in the manual order, the list
+    /// switches to it first if it was sorted some other way
+    fn move_feed(&mut self, up: bool) -> crate::error::Result<()> {
+        if self.order != FeedOrder::Manual {
+            self.set_order(FeedOrder::Manual);
+        }
+        let Some(i) = self.state.selected() else {
+            return Ok(());
+        };
+        let j = if up {
+            i.checked_sub(1)
+        } else {
+            Some(i + 1).filter(|j| *j < self.feeds.len())
+        };
+        let Some(j) = j else {
+            return Ok(());
+        };
+        self.feeds.swap(i, j);
+        self.unread_minutes.swap(i, j);
+        self.state.select(Some(j));
+
+        // Every feed gets a place, so the ones that never had one stay where they are
+        for (position, feed) in self.feeds.iter_mut().enumerate() {
+            let position = Some(position as i64);
+            if feed.position() != position {
+                update_one_feed(
+                    doc! {"_id": feed.id},
+                    doc! {"$set": {"position": position}},
+                    self.db,
+                )?;
+                feed.set_position(position);
+            }
+        }
+        Ok(())
+    }
+
+    fn count_unread_minutes(&mut self) {
+        let words_per_minute = self.config.reading.words_per_minute;
+        self.unread_minutes = self
+            .feeds
+            .iter()
+            .map(|feed| {
+                feed.stories()
+                    .iter()
+                    .filter(|story| !story.read)
+                    .map(|story| story.word_count())
+                    .sum::<usize>()
+                    .div_ceil(words_per_minute.max(1))
+            })
+            .collect();
+    }
+
+    pub fn selected(&self) -> Option<&Feed> {
+        self.feeds.get(self.state.selected()?)
+    }
+
+    /// Moves the selection onto `feed`, if it is still there
+    pub fn select_feed(&mut self, feed: &Feed) {
+        if let Some(i) = self.feeds.iter().position(|f| f.id == feed.id) {
+            self.state.select(Some(i));
+        }
+    }
+
+    /// Marks every story of the selected feed as read
+    fn mark_read(&mut self) -> crate::error::Result<()> {
+        if let Some(feed) = self.selected() {
+            self.undo.mark_feed_read(feed)?;
+            self.reload()?;
+        }
+        Ok(())
+    }
+
+    /// Does what was picked in the command palette
+    fn choose(&mut self, choice: Choice) -> crate::error::Result<MenuState> {
+        match choice {
+            Choice::Key(code) => self.transition(KeyEvent::from(code)),
+            Choice::Feed(id) => match self.feeds.iter().find(|feed| feed.id == id).cloned() {
+                Some(feed) => {
+                    self.select_feed(&feed);
+                    Ok(MenuState::Stories(Some(Box::new(feed))))
+                }
+                None => Ok(self.state()),
+            },
+        }
+    }
+
+    /// Fetches every feed that is due from the web again, a failing feed does not stop
+    /// the others
+    fn refresh_all(&mut self) -> crate::error::Result<()> {
+        let mut first_error = None;
+        let now = Utc::now();
+        let mut duplicates = Duplicates::new(&self.feeds);
+        let mut skipped = Vec::new();
+        for feed in &self.feeds {
+            if let Some(reason) = feed.not_due(now) {
+                skipped.push(format!("{}: {reason}", feed.title()));
+            } else if let Err(error) = self.refresh(feed.clone(), &mut duplicates) {
+                first_error.get_or_insert(error);
+            }
+        }
+
+        match skipped.as_slice() {
+            [] => {}
+            [reason] => self.notifier.tell(format!("Skipped {reason}")),
+            _ => self.notifier.tell(format!(
+                "Skipped {} feeds that are not due, ⇧G gets the selected one anyway",
+                skipped.len()
+            )),
+        }
+        self.reload()?;
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Refreshes the selected feed whether it is due or not
+    fn refresh_selected(&mut self) -> crate::error::Result<()> {
+        if let Some(feed) = self.state.selected().and_then(|i| self.feeds.get(i)) {
+            let mut duplicates = Duplicates::new(&self.feeds);
+            let refreshed = self.refresh(feed.clone(), &mut duplicates);
+            self.reload()?;
+            refreshed?;
+        }
+        Ok(())
+    }
+
+    /// Fetches one feed, downloads its new episodes and tells about its new stories
+    fn refresh(&self, mut feed: Feed, duplicates: &mut Duplicates) -> crate::error::Result<()> {
+        let report = refresh_feed(
+            &mut feed,
+            fetch_feed,
+            &self.config.retention,
+            duplicates,
+            self.db,
+        )?;
+        duplicates.add(&feed);
+        self.downloader.auto_download(&feed);
+        // Not worth stopping the refresh for, but told about like a failed feed
+        notify_new_stories(&feed, &report, &self.config.notifications)
+    }
+
+    /// Changes how many of the newest episodes of the selected feed are downloaded
+    fn change_auto_download(&mut self, more: bool) -> crate::error::Result<()> {
+        if let Some(feed) = self.state.selected().and_then(|i| self.feeds.get(i)) {
+            let auto_download = if more {
+                feed.auto_download() + 1
+            } else {
+                feed.auto_download().saturating_sub(1)
+            };
+            update_one_feed(
+                doc! {"_id": feed.id},
+                doc! {"$set": {"auto_download": auto_download as i64}},
+                self.db,
+            )?;
+            self.reload()?;
+        }
+        Ok(())
+    }
+
+    /// Turns fetching the full article of new stories on or off for the selected feed
+    fn toggle_full_text(&mut self) -> crate::error::Result<()> {
+        if let Some(feed) = self.state.selected().and_then(|i| self.feeds.get(i)) {
+            update_one_feed(
+                doc! {"_id": feed.id},
+                doc! {"$set": {"full_text": !feed.full_text()}},
+                self.db,
+            )?;
+            self.reload()?;
+        }
+        Ok(())
     }
 
     fn next(&mut self) {
@@ -86,81 +533,57 @@ impl<'a> FeedsMenu<'a> {
         };
         self.state.select(Some(i));
     }
-}
-
-impl<'a> Menu for FeedsMenu<'a> {
-    fn draw(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
-        let background = Block::default().style(Style::default().bg(one_dark(Color::Black)));
-        f.render_widget(background, f.size());
-
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(5)
-            .constraints([Constraint::Percentage(25), Constraint::Percentage(80)])
-            .split(f.size());
-
-        // COMMANDS BOX
-        let block = Block::default().title("Commands").borders(Borders::ALL);
-
-        f.render_widget(block, chunks[0]);
-
-        let subscribe_delete_spans = Spans::from(vec![
-            Span::styled("S D   ", Style::default().fg(one_dark(Color::Green))),
-            Span::raw("Subscribe/Delete Feed"),
-        ]);
-
-        let help_chunks = Layout::default()
-            .constraints(vec![Constraint::Percentage(100)])
-            .margin(2)
-            .split(chunks[0]);
-
-        let enter_spans = Spans::from(vec![
-            Span::styled("ENTER ", Style::default().fg(one_dark(Color::Green))),
-            Span::raw("Load Stories"),
-        ]);
-
-        let arrows_spans = Spans::from(vec![
-            Span::styled("↑ ↓   ", Style::default().fg(one_dark(Color::Green))),
-            Span::raw("Navigate Up and Down"),
-        ]);
-
-        let quit_spans = Spans::from(vec![
-            Span::styled("ESC   ", Style::default().fg(one_dark(Color::Green))),
-            Span::raw("Quit"),
-        ]);
-
-        let paragraph = Paragraph::new(vec![
-            subscribe_delete_spans,
-            enter_spans,
-            Spans::from(""),
-            arrows_spans,
-            quit_spans,
-        ])
-        .wrap(Wrap { trim: true });
-
-        f.render_widget(paragraph, help_chunks[0]);
-        // COMMANDS BOX
 
+    /// The feeds list alone, in `area`. Panes that are not focused get a dimmer border.
+    pub fn draw_main(
+        &mut self,
+        f: &mut Frame<CrosstermBackend<Stdout>>,
+        area: Rect,
+        focused: bool,
+    ) {
         // FEEDS LIST
-        let block = Block::default().title(self.title).borders(Borders::ALL);
+        let block = Block::default()
+            .title(format!("{} ({})", self.title, self.order.label()))
+            .borders(Borders::ALL)
+            .border_style(border_style(focused));
 
-        f.render_widget(block, chunks[1]);
+        f.render_widget(block, area);
 
         let feeds_chunks = Layout::default()
             .constraints(vec![Constraint::Percentage(100)])
             .margin(1)
-            .split(chunks[1]);
+            .split(area);
 
         let feeds: Vec<ListItem> = self
             .feeds
             .iter()
-            .map(|f| {
+            .zip(&self.unread_minutes)
+            .map(|(f, unread_minutes)| {
                 let story_count = f.stories().len().to_string();
                 let story_count = format!(" ({})", story_count);
-                let title_spans = Spans::from(vec![
+                let mut title_spans = vec![
                     Span::raw(f.title()),
                     Span::styled(story_count, Style::default().fg(one_dark(Color::Gray))),
-                ]);
+                ];
+                if *unread_minutes > 0 {
+                    title_spans.push(Span::styled(
+                        format!(" · {unread_minutes} min unread"),
+                        Style::default().fg(one_dark(Color::Gray)),
+                    ));
+                }
+                if f.auto_download() > 0 {
+                    title_spans.push(Span::styled(
+                        format!(" ⇩{}", f.auto_download()),
+                        Style::default().fg(one_dark(Color::Magenta)),
+                    ));
+                }
+                if f.full_text() {
+                    title_spans.push(Span::styled(
+                        " ¶",
+                        Style::default().fg(one_dark(Color::LightBlue)),
+                    ));
+                }
+                let title_spans = Spans::from(title_spans);
                 ListItem::new(title_spans)
             })
             .collect();
@@ -173,87 +596,178 @@ impl<'a> Menu for FeedsMenu<'a> {
                     .fg(one_dark(Color::Black)),
             );
 
+        self.list_area = feeds_chunks[0];
+        self.offset = list_offset(self.offset, self.state.selected(), self.list_area.height);
         f.render_stateful_widget(list, feeds_chunks[0], &mut self.state);
         // FEEDS LIST
+    }
+
+    /// Writes every feed to an OPML file in the data directory
+    fn export(&self) -> crate::error::Result<()> {
+        let dir = data_dir();
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("feeds.opml");
+        fs::write(&path, export_opml(&self.feeds))?;
+        self.notifier.tell(format!(
+            "Exported {} feeds to {}",
+            self.feeds.len(),
+            path.display()
+        ));
+        Ok(())
+    }
+
+    /// Whether the help or a popup is open
+    pub fn has_overlay(&self) -> bool {
+        self.help
+            || self.subscribe_popup.popped
+            || self.delete_feed_popup.popped
+            || self.edit_feed_popup.popped
+            || self.feed_info_popup.popped
+            || self.command_palette.popped
+    }
+
+    /// The help and popups, over everything else
+    pub fn draw_overlays(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
+        if self.help {
+            draw_help(f, Self::ACTIONS);
+        }
 
         // POPUP
         if self.subscribe_popup.popped {
             self.subscribe_popup.draw(f);
         } else if self.delete_feed_popup.popped {
             self.delete_feed_popup.draw(f);
+        } else if self.edit_feed_popup.popped {
+            self.edit_feed_popup.draw(f);
+        } else if self.feed_info_popup.popped {
+            self.feed_info_popup.draw(f);
+        } else if self.command_palette.popped {
+            self.command_palette.draw(f);
         }
         // POPUP
     }
+}
+
+impl<'a> Menu for FeedsMenu<'a> {
+    fn draw(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
+        let background = Block::default().style(Style::default().bg(one_dark(Color::Black)));
+        f.render_widget(background, f.size());
+
+        let layout = MenuLayout::new(f.size());
+        self.draw_main(f, layout.main, true);
+        draw_commands(f, &layout, Self::ACTIONS);
+        self.draw_overlays(f);
+    }
 
-    fn observer(&mut self) {
+    fn observer(&mut self) -> crate::error::Result<()> {
         if self.subscribe_popup.subscribed {
+            // Reset the flag first so a failed insert is not retried forever
+            self.subscribe_popup.subscribed = false;
             if let Some(feed) = self.subscribe_popup.feed() {
-                insert_one_feed(feed, self.db).unwrap();
-                self.reload().unwrap();
-                self.subscribe_popup.subscribed = false;
+                subscribe(feed, self.db)?;
+                self.reload()?;
             }
             self.delete_feed_popup.deleted = false;
         }
 
+        if let Some(feed) = self.edit_feed_popup.take_edited() {
+            update_one_feed(
+                doc! {"_id": feed.id},
+                doc! {"$set": {
+                    "custom_title": feed.custom_title(),
+                    "rss_link": feed.rss_link(),
+                    "refresh_minutes": feed.refresh_minutes(),
+                    "auto_download": feed.auto_download() as i64,
+                    "full_text": feed.full_text(),
+                    "notify": feed.notify(),
+                }},
+                self.db,
+            )?;
+            self.reload()?;
+        }
+
         if self.delete_feed_popup.deleted {
+            self.delete_feed_popup.deleted = false;
             if let Some(selected) = self.state.selected() {
                 if let Some(feed) = self.feeds.get(selected) {
-                    delete_one_feed(doc! {"_id": feed.id}, self.db).unwrap();
-                    self.reload().unwrap();
+                    self.undo.delete_feed(feed)?;
+                    self.reload()?;
                 }
             }
-            self.delete_feed_popup.deleted = false;
         }
+        Ok(())
     }
 
-    fn transition(&mut self, key_event: KeyEvent) -> MenuState {
+    fn transition(&mut self, key_event: KeyEvent) -> crate::error::Result<MenuState> {
         if self.subscribe_popup.popped {
-            self.subscribe_popup.transition(key_event);
+            self.subscribe_popup.transition(key_event)?;
         } else if self.delete_feed_popup.popped {
-            self.delete_feed_popup.transition(key_event);
+            self.delete_feed_popup.transition(key_event)?;
+        } else if self.edit_feed_popup.popped {
+            self.edit_feed_popup.transition(key_event)?;
+        } else if self.feed_info_popup.popped {
+            self.feed_info_popup.transition(key_event)?;
+        } else if self.command_palette.popped {
+            self.command_palette.transition(key_event)?;
+            if let Some(choice) = self.command_palette.take_chosen() {
+                return self.choose(choice);
+            }
+        } else if self.help {
+            // Any of the keys that usually close something closes the help
+            if let KeyCode::Esc | KeyCode::Enter | KeyCode::Char('?') = key_event.code {
+                self.help = false;
+            }
+        } else if opens_palette(&key_event) {
+            self.command_palette.open(Self::ACTIONS, &self.feeds);
+        } else if let Some(run) = action_for(Self::ACTIONS, key_event.code) {
+            return run(self);
         } else {
             match key_event.code {
-                KeyCode::Esc => {
-                    return MenuState::Exit;
-                }
-
-                KeyCode::Up => {
-                    self.previous();
-                }
-
-                KeyCode::Down => {
-                    self.next();
-                }
+                KeyCode::Up => self.previous(),
+                KeyCode::Down => self.next(),
+                _ => {}
+            }
+        }
+        // Fallback if none of the keys were pressed
+        Ok(self.state())
+    }
 
-                KeyCode::Enter => {
-                    if let Some(selected) = self.state.selected() {
-                        if let Some(feed) = self.feeds.get(selected) {
-                            return MenuState::Stories(Some(feed.clone()));
+    fn mouse(&mut self, mouse_event: MouseEvent) -> crate::error::Result<MenuState> {
+        if self.subscribe_popup.popped {
+            self.subscribe_popup.mouse(mouse_event)?;
+        } else if self.delete_feed_popup.popped {
+            self.delete_feed_popup.mouse(mouse_event)?;
+        } else if self.edit_feed_popup.popped {
+            self.edit_feed_popup.mouse(mouse_event)?;
+        } else if self.feed_info_popup.popped {
+            self.feed_info_popup.mouse(mouse_event)?;
+        } else if !self.command_palette.popped {
+            match mouse_event.kind {
+                MouseEventKind::ScrollDown => self.next(),
+                MouseEventKind::ScrollUp => self.previous(),
+                MouseEventKind::Down(MouseButton::Left) => {
+                    let clicked = item_at(
+                        self.list_area,
+                        self.offset,
+                        mouse_event.column,
+                        mouse_event.row,
+                    )
+                    .filter(|i| *i < self.feeds.len());
+                    if let Some(i) = clicked {
+                        self.state.select(Some(i));
+                        if self.clicks.double(i) {
+                            return self.transition(KeyEvent::from(KeyCode::Enter));
                         }
                     }
                 }
-
-                KeyCode::Char('s') => {
-                    self.subscribe_popup.popped = true;
-                }
-
-                KeyCode::Char('r') => {
-                    self.reload().unwrap();
-                }
-
-                KeyCode::Char('d') => {
-                    self.delete_feed_popup.popped = true;
-                }
-
                 _ => {}
             }
         }
-        // Fallback if none of the keys were pressed
-        self.state()
+        Ok(self.state())
     }
 
     fn reload(&mut self) -> crate::error::Result<()> {
-        self.feeds = find_many_feed(None, self.db)?;
+        self.set_feeds(find_many_feed(None, self.db)?);
         Ok(())
     }
 
@@ -261,3 +775,13 @@ impl<'a> Menu for FeedsMenu<'a> {
         MenuState::Feeds
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_help_shows_the_keys_that_run_something() {
+        crate::menus::tests::assert_actions_match_keys(FeedsMenu::ACTIONS);
+    }
+}
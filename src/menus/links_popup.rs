@@ -0,0 +1,276 @@
+use tui::backend::CrosstermBackend;
+use tui::layout::{Constraint, Layout, Rect};
+use tui::style::{Color, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap};
+use tui::Frame;
+
+use std::io::Stdout;
+
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+
+use crate::config::Config;
+use crate::models::Image;
+use crate::notifier::Notifier;
+use crate::util::copy_to_clipboard;
+
+use super::{
+    centered_rect, item_at, list_offset, one_dark, open_image, open_in_browser, Clicks, Menu,
+    MenuState,
+};
+
+pub struct LinksPopup<'a> {
+    title: &'a str,
+    links: Vec<String>,
+    /// Alt text of the images, when the links are images
+    labels: Vec<Option<String>>,
+    images: bool,
+    state: ListState,
+    /// Where the list was drawn and the first link it showed, for the mouse
+    list_area: Rect,
+    offset: usize,
+    clicks: Clicks,
+    message: Option<String>,
+
+    pub popped: bool,
+    subscribe: Option<String>,
+
+    config: &'a Config,
+    notifier: &'a Notifier,
+}
+
+impl<'a> LinksPopup<'a> {
+    pub fn new(config: &'a Config, notifier: &'a Notifier) -> Self {
+        LinksPopup {
+            title: "Links in This Story",
+            links: vec![],
+            labels: vec![],
+            images: false,
+            state: ListState::default(),
+            list_area: Rect::default(),
+            offset: 0,
+            clicks: Clicks::default(),
+            message: None,
+            popped: false,
+            subscribe: None,
+            config,
+            notifier,
+        }
+    }
+
+    pub fn set_links(&mut self, links: impl Into<Vec<String>>) {
+        self.title = "Links in This Story";
+        self.links = links.into();
+        self.labels.clear();
+        self.images = false;
+        self.state
+            .select(if self.links.is_empty() { None } else { Some(0) });
+    }
+
+    /// Lists images instead of links, they open in the image viewer
+    pub fn set_images(&mut self, images: &[Image]) {
+        self.title = "Images in This Story";
+        self.links = images.iter().map(|image| image.url().to_string()).collect();
+        self.labels = images
+            .iter()
+            .map(|image| image.alt().map(String::from))
+            .collect();
+        self.images = true;
+        self.state
+            .select(if self.links.is_empty() { None } else { Some(0) });
+    }
+
+    pub fn set_message(&mut self, message: impl Into<String>) {
+        self.message = Some(message.into());
+    }
+
+    /// The link that should be subscribed to, if one was picked
+    pub fn take_subscribe(&mut self) -> Option<String> {
+        self.subscribe.take()
+    }
+
+    fn selected(&self) -> Option<&String> {
+        self.links.get(self.state.selected()?)
+    }
+
+    fn next(&mut self) {
+        if self.links.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) if i + 1 < self.links.len() => i + 1,
+            _ => 0,
+        };
+        self.state.select(Some(i));
+    }
+
+    fn previous(&mut self) {
+        if self.links.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(0) | None => self.links.len() - 1,
+            Some(i) => i - 1,
+        };
+        self.state.select(Some(i));
+    }
+}
+
+impl<'a> Menu for LinksPopup<'a> {
+    fn draw(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
+        let popup_area = centered_rect(60, 50, f.size());
+
+        let block = Block::default()
+            .title(self.title)
+            .borders(Borders::ALL)
+            .style(Style::default().bg(one_dark(Color::DarkGray)));
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .constraints(vec![
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .margin(1)
+            .horizontal_margin(2)
+            .split(popup_area);
+
+        let links: Vec<ListItem> = self
+            .links
+            .iter()
+            .enumerate()
+            .map(|(i, link)| {
+                let mut spans = vec![Span::styled(
+                    format!("[{}] ", i + 1),
+                    Style::default().fg(one_dark(Color::Gray)),
+                )];
+                if let Some(Some(label)) = self.labels.get(i) {
+                    spans.push(Span::raw(format!("{label} ")));
+                    spans.push(Span::styled(
+                        link.as_str(),
+                        Style::default().fg(one_dark(Color::Gray)),
+                    ));
+                } else {
+                    spans.push(Span::raw(link.as_str()));
+                }
+                ListItem::new(Spans::from(spans))
+            })
+            .collect();
+
+        let list = List::new(links)
+            .style(Style::default().fg(one_dark(Color::White)))
+            .highlight_style(
+                Style::default()
+                    .bg(one_dark(Color::LightBlue))
+                    .fg(one_dark(Color::Black)),
+            );
+        self.list_area = chunks[0];
+        self.offset = list_offset(self.offset, self.state.selected(), self.list_area.height);
+        f.render_stateful_widget(list, chunks[0], &mut self.state);
+
+        let message = Paragraph::new(self.message.as_deref().unwrap_or_default())
+            .style(Style::default().fg(one_dark(Color::Yellow)))
+            .wrap(Wrap { trim: true });
+        f.render_widget(message, chunks[1]);
+
+        let mut help = vec![
+            Span::styled("ENTER ", Style::default().fg(one_dark(Color::Green))),
+            Span::raw("Open  "),
+            Span::styled("C ", Style::default().fg(one_dark(Color::Green))),
+            Span::raw("Copy  "),
+        ];
+        // Images are no feeds
+        if !self.images {
+            help.push(Span::styled(
+                "S ",
+                Style::default().fg(one_dark(Color::Green)),
+            ));
+            help.push(Span::raw("Subscribe  "));
+        }
+        help.push(Span::styled(
+            "ESC ",
+            Style::default().fg(one_dark(Color::Green)),
+        ));
+        help.push(Span::raw("Back"));
+        let help = Paragraph::new(Spans::from(help));
+        f.render_widget(help, chunks[2]);
+    }
+
+    fn transition(&mut self, key_event: KeyEvent) -> crate::error::Result<MenuState> {
+        match key_event.code {
+            KeyCode::Esc => {
+                self.reload()?;
+            }
+
+            KeyCode::Up => {
+                self.previous();
+            }
+
+            KeyCode::Down => {
+                self.next();
+            }
+
+            KeyCode::Enter => match self.selected() {
+                Some(image) if self.images => open_image(image, self.config, self.notifier)?,
+                Some(link) => open_in_browser(link, self.config, self.notifier)?,
+                None => {}
+            },
+
+            KeyCode::Char('c') => {
+                if let Some(link) = self.selected().cloned() {
+                    copy_to_clipboard(&link)?;
+                    self.set_message("Copied to the clipboard");
+                }
+            }
+
+            KeyCode::Char('s') if !self.images => {
+                self.subscribe = self.selected().cloned();
+            }
+
+            _ => {}
+        }
+        // Fallback if none of the keys were pressed
+        Ok(self.state())
+    }
+
+    fn mouse(&mut self, mouse_event: MouseEvent) -> crate::error::Result<MenuState> {
+        match mouse_event.kind {
+            MouseEventKind::ScrollDown => self.next(),
+            MouseEventKind::ScrollUp => self.previous(),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let clicked = item_at(
+                    self.list_area,
+                    self.offset,
+                    mouse_event.column,
+                    mouse_event.row,
+                )
+                .filter(|i| *i < self.links.len());
+                if let Some(i) = clicked {
+                    self.state.select(Some(i));
+                    if self.clicks.double(i) {
+                        return self.transition(KeyEvent::from(KeyCode::Enter));
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(self.state())
+    }
+
+    fn reload(&mut self) -> crate::error::Result<()> {
+        self.popped = false;
+        self.message = None;
+        self.subscribe = None;
+        Ok(())
+    }
+
+    fn state(&mut self) -> MenuState {
+        MenuState::Contents(None)
+    }
+
+    fn observer(&mut self) -> crate::error::Result<()> {
+        Ok(())
+    }
+}
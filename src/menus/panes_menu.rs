@@ -0,0 +1,285 @@
+use tui::backend::CrosstermBackend;
+use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::style::{Color, Style};
+use tui::widgets::Block;
+use tui::Frame;
+
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+
+use std::io::Stdout;
+
+use mongodb::sync::Database;
+
+use crate::config::Config;
+use crate::download::Downloader;
+use crate::notifier::Notifier;
+use crate::undo::UndoStack;
+
+use super::{
+    draw_footer, item_at, one_dark, ContentsMenu, FeedsMenu, Menu, MenuState, StoriesMenu,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Pane {
+    Feeds,
+    Stories,
+    Contents,
+}
+
+impl Pane {
+    fn next(self) -> Pane {
+        match self {
+            Pane::Feeds => Pane::Stories,
+            Pane::Stories => Pane::Contents,
+            Pane::Contents => Pane::Feeds,
+        }
+    }
+
+    fn previous(self) -> Pane {
+        match self {
+            Pane::Feeds => Pane::Contents,
+            Pane::Stories => Pane::Feeds,
+            Pane::Contents => Pane::Stories,
+        }
+    }
+}
+
+/// Feeds, the stories of the selected feed and the selected story side by side.
+/// Every pane is the menu of its own, keys go to the one with the focus.
+pub struct PanesMenu<'a> {
+    feeds_menu: FeedsMenu<'a>,
+    stories_menu: StoriesMenu<'a>,
+    contents_menu: ContentsMenu<'a>,
+
+    focus: Pane,
+    /// Where the panes were drawn, for the mouse
+    areas: [Rect; 3],
+}
+
+impl<'a> PanesMenu<'a> {
+    pub fn new(
+        db: &'a Database,
+        config: &'a Config,
+        downloader: &'a Downloader,
+        undo: &'a UndoStack,
+        notifier: &'a Notifier,
+    ) -> Self {
+        PanesMenu {
+            feeds_menu: FeedsMenu::new(db, config, downloader, undo, notifier),
+            stories_menu: StoriesMenu::new(db, config, downloader, undo, notifier),
+            contents_menu: ContentsMenu::new(db, config, downloader, undo, notifier),
+
+            focus: Pane::Feeds,
+            areas: [Rect::default(); 3],
+        }
+    }
+
+    /// Shows the stories of the selected feed and a preview of the selected story,
+    /// whenever the selection moved on
+    fn follow_selection(&mut self) {
+        if let Some(feed) = self.feeds_menu.selected() {
+            if feed.id != self.stories_menu.feed().id {
+                let feed = feed.clone();
+                let first = feed.stories().first().cloned();
+                self.stories_menu.set_feed(feed);
+                if let Some(first) = first {
+                    self.stories_menu.select_story(&first);
+                }
+            }
+        }
+
+        if let Some(story) = self.stories_menu.selected() {
+            if story.id != self.contents_menu.story().id {
+                let story = story.clone();
+                self.contents_menu.set_story(story);
+                self.contents_menu
+                    .set_feed(self.stories_menu.feed().clone());
+            }
+        }
+    }
+
+    /// The reading pane can move on to other stories and feeds, the other panes follow it
+    fn follow_contents(&mut self) {
+        if self.contents_menu.feed().id != self.stories_menu.feed().id {
+            self.feeds_menu.select_feed(self.contents_menu.feed());
+            self.stories_menu
+                .set_feed(self.contents_menu.feed().clone());
+        }
+        self.stories_menu.select_story(self.contents_menu.story());
+    }
+
+    fn focus(&mut self, pane: Pane) -> crate::error::Result<()> {
+        // Read counts could have changed in the other panes
+        if pane == Pane::Feeds && self.focus != Pane::Feeds {
+            self.feeds_menu.reload()?;
+        }
+        self.focus = pane;
+        Ok(())
+    }
+
+    /// Undoing in one pane can bring back feeds and unread stories the others show
+    fn refresh_after_undo(&mut self) -> crate::error::Result<()> {
+        self.feeds_menu.reload()?;
+        let shown = self.stories_menu.feed().id;
+        if self.feeds_menu.feeds().iter().any(|feed| feed.id == shown) {
+            self.stories_menu.reload()?;
+        }
+        Ok(())
+    }
+
+    /// What the focused pane asked for, in terms of panes
+    fn settle(&mut self, state: MenuState) -> crate::error::Result<MenuState> {
+        match state {
+            MenuState::Exit => return Ok(MenuState::Exit),
+            MenuState::Feeds => self.focus(Pane::Feeds)?,
+            MenuState::Stories(feed) => {
+                if let Some(feed) = feed {
+                    self.feeds_menu.select_feed(&feed);
+                    self.stories_menu.set_feed(*feed);
+                } else if self.focus == Pane::Contents {
+                    self.follow_contents();
+                }
+                self.focus(Pane::Stories)?;
+            }
+            MenuState::Contents(story) => {
+                if let Some(story) = story {
+                    // Marked as read on the way, show that in the list
+                    self.stories_menu.reload()?;
+                    self.contents_menu.set_story(*story);
+                    self.contents_menu
+                        .set_feed(self.stories_menu.feed().clone());
+                    self.focus(Pane::Contents)?;
+                } else {
+                    self.follow_contents();
+                }
+            }
+            MenuState::Panes => {}
+        }
+        Ok(self.state())
+    }
+
+    /// Whether the focused pane has the help or a popup open
+    fn has_overlay(&self) -> bool {
+        match self.focus {
+            Pane::Feeds => self.feeds_menu.has_overlay(),
+            Pane::Stories => self.stories_menu.has_overlay(),
+            Pane::Contents => self.contents_menu.has_overlay(),
+        }
+    }
+
+    fn pane_at(&self, column: u16, row: u16) -> Option<Pane> {
+        let panes = [Pane::Feeds, Pane::Stories, Pane::Contents];
+        panes
+            .into_iter()
+            .zip(self.areas)
+            .find_map(|(pane, area)| item_at(area, 0, column, row).map(|_| pane))
+    }
+}
+
+impl<'a> Menu for PanesMenu<'a> {
+    fn draw(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
+        let background = Block::default().style(Style::default().bg(one_dark(Color::Black)));
+        f.render_widget(background, f.size());
+
+        // Leave the bottom row to the status bar
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(f.size());
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(20),
+                Constraint::Percentage(35),
+                Constraint::Percentage(45),
+            ])
+            .split(rows[0]);
+        self.areas = [columns[0], columns[1], columns[2]];
+
+        self.feeds_menu
+            .draw_main(f, columns[0], self.focus == Pane::Feeds);
+        self.stories_menu
+            .draw_main(f, columns[1], self.focus == Pane::Stories);
+        self.contents_menu
+            .draw_main(f, columns[2], self.focus == Pane::Contents);
+
+        draw_footer(
+            f,
+            rows[1],
+            vec![("TAB", "Next Pane"), ("?", "Commands"), ("ESC", "Quit")],
+        );
+
+        match self.focus {
+            Pane::Feeds => self.feeds_menu.draw_overlays(f),
+            Pane::Stories => self.stories_menu.draw_overlays(f),
+            Pane::Contents => self.contents_menu.draw_overlays(f),
+        }
+    }
+
+    fn transition(&mut self, key_event: KeyEvent) -> crate::error::Result<MenuState> {
+        let undoing = key_event.code == KeyCode::Char('u') && !self.has_overlay();
+        let state = match key_event.code {
+            KeyCode::Tab if !self.has_overlay() => {
+                self.focus(self.focus.next())?;
+                return Ok(self.state());
+            }
+            KeyCode::BackTab if !self.has_overlay() => {
+                self.focus(self.focus.previous())?;
+                return Ok(self.state());
+            }
+            _ => match self.focus {
+                Pane::Feeds => self.feeds_menu.transition(key_event)?,
+                Pane::Stories => self.stories_menu.transition(key_event)?,
+                Pane::Contents => self.contents_menu.transition(key_event)?,
+            },
+        };
+        if undoing {
+            self.refresh_after_undo()?;
+        }
+        self.settle(state)
+    }
+
+    fn mouse(&mut self, mouse_event: MouseEvent) -> crate::error::Result<MenuState> {
+        // Popups take the mouse wherever it is, the pane under it does otherwise
+        let pane = match self.pane_at(mouse_event.column, mouse_event.row) {
+            Some(pane) if !self.has_overlay() => pane,
+            _ => self.focus,
+        };
+        if pane != self.focus {
+            self.focus(pane)?;
+        }
+        let state = match pane {
+            Pane::Feeds => self.feeds_menu.mouse(mouse_event)?,
+            Pane::Stories => self.stories_menu.mouse(mouse_event)?,
+            Pane::Contents => self.contents_menu.mouse(mouse_event)?,
+        };
+        self.settle(state)
+    }
+
+    fn observer(&mut self) -> crate::error::Result<()> {
+        self.feeds_menu.observer()?;
+        self.stories_menu.observer()?;
+        self.contents_menu.observer()?;
+        self.follow_selection();
+        Ok(())
+    }
+
+    fn reload(&mut self) -> crate::error::Result<()> {
+        self.feeds_menu.reload()?;
+        if self.feeds_menu.selected().is_none() {
+            if let Some(first) = self.feeds_menu.feeds().first().cloned() {
+                self.feeds_menu.select_feed(&first);
+            }
+        }
+        Ok(())
+    }
+
+    fn state(&mut self) -> MenuState {
+        MenuState::Panes
+    }
+}
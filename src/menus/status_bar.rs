@@ -0,0 +1,71 @@
+use tui::backend::CrosstermBackend;
+use tui::layout::Rect;
+use tui::style::{Color, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::Paragraph;
+use tui::Frame;
+
+use std::io::Stdout;
+use std::time::{Duration, Instant};
+
+use super::one_dark;
+
+const STATUS_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy)]
+enum Level {
+    Error,
+    Info,
+}
+
+pub struct StatusBar {
+    message: Option<(String, Level, Instant)>,
+}
+
+impl StatusBar {
+    pub fn new() -> Self {
+        StatusBar { message: None }
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.message = Some((message.into(), Level::Error, Instant::now()));
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.message = Some((message.into(), Level::Info, Instant::now()));
+    }
+
+    pub fn draw(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
+        // Messages only linger for a little while
+        if let Some((_, _, since)) = &self.message {
+            if since.elapsed() > STATUS_TIMEOUT {
+                self.message = None;
+            }
+        }
+
+        let size = f.size();
+        if let Some((message, level, _)) = &self.message {
+            if size.height == 0 {
+                return;
+            }
+
+            let (label, color) = match level {
+                Level::Error => (" ERROR ", Color::Red),
+                Level::Info => (" INFO ", Color::Green),
+            };
+            let spans = Spans::from(vec![
+                Span::styled(
+                    label,
+                    Style::default()
+                        .bg(one_dark(color))
+                        .fg(one_dark(Color::Black)),
+                ),
+                Span::raw(" "),
+                Span::styled(message.as_str(), Style::default().fg(one_dark(color))),
+            ]);
+
+            let area = Rect::new(size.x, size.y + size.height - 1, size.width, 1);
+            f.render_widget(Paragraph::new(spans), area);
+        }
+    }
+}
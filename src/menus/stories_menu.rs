@@ -1,37 +1,246 @@
 use tui::backend::CrosstermBackend;
-use tui::layout::{Constraint, Direction, Layout};
+use tui::layout::{Constraint, Layout, Rect};
 use tui::style::{Color, Modifier, Style};
 use tui::terminal::Frame;
 use tui::text::{Span, Spans};
-use tui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap};
+use tui::widgets::{Block, Borders, Cell, Row, Table, TableState};
 
 use mongodb::bson::doc;
 use mongodb::sync::Database;
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 
 use std::io::Stdout;
 
-use crate::models::{find_one_feed, update_one_feed, Feed};
+use crate::config::Config;
+use crate::download::{DownloadState, Downloader};
+use crate::error::Error;
+use crate::models::{find_many_feed, find_one_feed, update_one_feed, Duplicates, Feed, Story};
+use crate::notifier::Notifier;
+use crate::undo::UndoStack;
+use crate::util::copy_to_clipboard;
 
-use super::{one_dark, Menu, MenuState};
+use super::{
+    action_for, border_style, draw_commands, draw_help, format_duration, item_at, list_offset,
+    one_dark, opens_palette, play_episode, switch_theme, title_and_link, Action, CategoryPopup,
+    Choice, Clicks, CommandPalette, Menu, MenuLayout, MenuState,
+};
 
 pub struct StoriesMenu<'a> {
     title: &'a str,
     feed: Feed,
     state: TableState,
+    /// The help with every command is open
+    help: bool,
+    /// Reading time of every story, counted once when the feed is loaded
+    reading_minutes: Vec<usize>,
+    /// Other feeds every story is in as well, looked up again when the feed changes
+    also_in: Vec<Vec<String>>,
+    duplicates_stale: bool,
+    /// The stories of every feed, read again when a feed is set from outside, where
+    /// feeds could have been refreshed, renamed or deleted meanwhile
+    duplicates: Option<Duplicates>,
+    /// Where the rows were drawn and the first story they showed, for the mouse
+    rows_area: Rect,
+    offset: usize,
+    clicks: Clicks,
+
+    /// Only stories in this category are shown
+    category: Option<String>,
+    /// Categories of every story of the feed and how many stories are in each
+    categories: Vec<(String, usize)>,
+    /// Positions in the feed of the stories shown, which rows of the table are
+    visible: Vec<usize>,
+
+    category_popup: CategoryPopup<'a>,
+    command_palette: CommandPalette<'a>,
 
     db: &'a Database,
+    config: &'a Config,
+    downloader: &'a Downloader,
+    undo: &'a UndoStack,
+    notifier: &'a Notifier,
 }
 
 impl<'a> StoriesMenu<'a> {
-    pub fn new(db: &'a Database) -> Self {
+    const ACTIONS: &'a [Action<Self>] = &[
+        Action {
+            keys: "←",
+            description: "Go Back",
+            key: Some(KeyCode::Left),
+            run: Some(|_| Ok(MenuState::Feeds)),
+        },
+        Action {
+            keys: "ENTER",
+            description: "Load Story",
+            key: Some(KeyCode::Enter),
+            run: Some(|menu| match menu.selected() {
+                Some(story) => {
+                    menu.undo.mark_story_read(story)?;
+                    Ok(MenuState::Contents(Some(Box::new(story.clone()))))
+                }
+                None => Ok(menu.state()),
+            }),
+        },
+        Action {
+            keys: "S",
+            description: "Star/Unstar Story",
+            key: Some(KeyCode::Char('s')),
+            run: Some(|menu| {
+                menu.toggle_star()?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "M",
+            description: "Mark Every Story of the Feed Read",
+            key: Some(KeyCode::Char('m')),
+            run: Some(|menu| {
+                // All of the feed, not only the stories in the category
+                menu.undo.mark_feed_read(&menu.feed)?;
+                menu.reload()?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "C",
+            description: "Filter Stories by Category",
+            key: Some(KeyCode::Char('c')),
+            run: Some(|menu| {
+                menu.category_popup.open(
+                    menu.categories.clone(),
+                    menu.feed.stories_len(),
+                    menu.category.as_deref(),
+                );
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "U",
+            description: "Undo Deleting a Feed or Marking Stories Read",
+            key: Some(KeyCode::Char('u')),
+            run: Some(|menu| {
+                menu.undo.undo()?;
+                // A deleted feed that comes back has stories of its own
+                menu.duplicates = None;
+                menu.reload()?;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "P",
+            description: "Play Episode",
+            key: Some(KeyCode::Char('p')),
+            run: Some(|menu| {
+                if let Some(story) = menu.selected() {
+                    play_episode(story, menu.config, menu.downloader, menu.notifier)?;
+                }
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "D",
+            description: "Download Episode",
+            key: Some(KeyCode::Char('d')),
+            run: Some(|menu| {
+                if let Some(story) = menu.selected() {
+                    menu.downloader.download(&menu.feed, story);
+                }
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "Y",
+            description: "Copy Link",
+            key: Some(KeyCode::Char('y')),
+            run: Some(|menu| {
+                if let Some(story) = menu.selected() {
+                    copy_to_clipboard(story.link().unwrap_or_default())?;
+                }
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "⇧Y",
+            description: "Copy Title and Link",
+            key: Some(KeyCode::Char('Y')),
+            run: Some(|menu| {
+                if let Some(story) = menu.selected() {
+                    copy_to_clipboard(&title_and_link(story))?;
+                }
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "↑ ↓",
+            description: "Navigate Up and Down",
+            key: None,
+            run: None,
+        },
+        Action {
+            keys: "⇧T",
+            description: "Switch Theme",
+            key: Some(KeyCode::Char('T')),
+            run: Some(|menu| {
+                switch_theme(menu.notifier);
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: ": ^P",
+            description: "Command Palette",
+            key: None,
+            run: None,
+        },
+        Action {
+            keys: "?",
+            description: "Show All Commands",
+            key: Some(KeyCode::Char('?')),
+            run: Some(|menu| {
+                menu.help = true;
+                Ok(menu.state())
+            }),
+        },
+        Action {
+            keys: "ESC",
+            description: "Quit",
+            key: Some(KeyCode::Esc),
+            run: Some(|_| Ok(MenuState::Exit)),
+        },
+    ];
+
+    pub fn new(
+        db: &'a Database,
+        config: &'a Config,
+        downloader: &'a Downloader,
+        undo: &'a UndoStack,
+        notifier: &'a Notifier,
+    ) -> Self {
         StoriesMenu {
             title: "Your Stories",
             feed: Feed::default(),
             state: TableState::default(),
+            help: false,
+            reading_minutes: vec![],
+            also_in: vec![],
+            duplicates_stale: false,
+            duplicates: None,
+            rows_area: Rect::default(),
+            offset: 0,
+            clicks: Clicks::default(),
+
+            category: None,
+            categories: vec![],
+            visible: vec![],
+
+            category_popup: CategoryPopup::new(),
+            command_palette: CommandPalette::new(),
 
             db,
+            config,
+            downloader,
+            undo,
+            notifier,
         }
     }
 
@@ -40,13 +249,134 @@ impl<'a> StoriesMenu<'a> {
     }
 
     pub fn set_feed(&mut self, feed: impl Into<Feed>) {
-        self.feed = feed.into()
+        let feed = feed.into();
+        // Categories belong to a feed
+        if feed.id != self.feed.id {
+            self.category = None;
+        }
+        self.feed = feed;
+        self.filter_by_category();
+        self.count_reading_minutes();
+        self.duplicates = None;
+        self.duplicates_stale = true;
+    }
+
+    /// Counts the categories of the feed, then shows only the stories in the chosen one.
+    /// The feed itself keeps every story.
+    fn filter_by_category(&mut self) {
+        let mut categories: Vec<(String, usize)> = vec![];
+        for story in self.feed.stories() {
+            for category in story.categories() {
+                match categories
+                    .iter_mut()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(category))
+                {
+                    Some((_, count)) => *count += 1,
+                    None => categories.push((category.clone(), 1)),
+                }
+            }
+        }
+        categories.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        self.categories = categories;
+
+        let category = self.category.as_deref();
+        self.visible = self
+            .feed
+            .stories()
+            .iter()
+            .enumerate()
+            .filter(|(_, story)| category.is_none_or(|category| story.has_category(category)))
+            .map(|(i, _)| i)
+            .collect();
+    }
+
+    /// The story in a row of the table
+    fn story_at(&self, row: usize) -> Option<&Story> {
+        self.feed.stories().get(*self.visible.get(row)?)
+    }
+
+    /// Shows the stories of the category picked in the popup, if one was
+    fn take_category(&mut self) -> crate::error::Result<()> {
+        if let Some(category) = self.category_popup.take_chosen() {
+            self.category = category;
+            self.filter_by_category();
+            self.state.select(if self.visible.is_empty() {
+                None
+            } else {
+                Some(0)
+            });
+        }
+        Ok(())
+    }
+
+    /// Looks for the stories of the feed in every other feed
+    fn find_duplicates(&mut self) -> crate::error::Result<()> {
+        // Tried once per change of the feed, a failure is not repeated on every frame
+        self.duplicates_stale = false;
+        let duplicates = match self.duplicates.take() {
+            Some(duplicates) => duplicates,
+            None => Duplicates::new(&find_many_feed(None, self.db)?),
+        };
+        self.also_in = self
+            .feed
+            .stories()
+            .iter()
+            .map(|story| duplicates.also_in(story, &self.feed))
+            .collect();
+        self.duplicates = Some(duplicates);
+        Ok(())
+    }
+
+    fn count_reading_minutes(&mut self) {
+        let words_per_minute = self.config.reading.words_per_minute;
+        self.reading_minutes = self
+            .feed
+            .stories()
+            .iter()
+            .map(|story| story.reading_minutes(words_per_minute))
+            .collect();
+    }
+
+    /// Moves the selection onto `story`, if it is shown
+    pub fn select_story(&mut self, story: &Story) {
+        let stories = self.feed.stories();
+        if let Some(row) = self.visible.iter().position(|i| stories[*i].id == story.id) {
+            self.state.select(Some(row));
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Story> {
+        self.story_at(self.state.selected()?)
+    }
+
+    /// Stars the selected story, or takes the star away
+    fn toggle_star(&mut self) -> crate::error::Result<()> {
+        if let Some(story) = self.selected() {
+            update_one_feed(
+                doc! {
+                    "stories._id": story.id
+                },
+                doc! {
+                    "$set": {
+                        "stories.$.starred": !story.starred
+                    }
+                },
+                self.db,
+            )?;
+            self.reload()?;
+        }
+        Ok(())
     }
 
     fn next(&mut self) {
+        if self.visible.is_empty() {
+            self.state.select(None);
+            return;
+        }
+
         let i = match self.state.selected() {
             Some(i) => {
-                if i >= self.feed.stories().len() - 1 {
+                if i >= self.visible.len() - 1 {
                     0
                 } else {
                     i + 1
@@ -58,10 +388,15 @@ impl<'a> StoriesMenu<'a> {
     }
 
     fn previous(&mut self) {
+        if self.visible.is_empty() {
+            self.state.select(None);
+            return;
+        }
+
         let i = match self.state.selected() {
             Some(i) => {
                 if i == 0 {
-                    self.feed.stories().len() - 1
+                    self.visible.len() - 1
                 } else {
                     i - 1
                 }
@@ -70,165 +405,297 @@ impl<'a> StoriesMenu<'a> {
         };
         self.state.select(Some(i));
     }
-}
-
-impl<'a> Menu for StoriesMenu<'a> {
-    fn draw(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
-        let background = Block::default().style(Style::default().bg(one_dark(Color::Black)));
-        f.render_widget(background, f.size());
-
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(5)
-            .constraints(vec![Constraint::Percentage(25), Constraint::Percentage(80)])
-            .split(f.size());
-
-        // COMMANDS BOX
-        let block = Block::default().title("Commands").borders(Borders::ALL);
-
-        f.render_widget(block, chunks[0]);
-
-        let help_chunks = Layout::default()
-            .constraints(vec![Constraint::Percentage(100)])
-            .margin(2)
-            .split(chunks[0]);
-
-        let back_spans = Spans::from(vec![
-            Span::styled("←     ", Style::default().fg(one_dark(Color::Green))),
-            Span::raw("Go Back"),
-        ]);
-
-        let arrows_spans = Spans::from(vec![
-            Span::styled("↑ ↓   ", Style::default().fg(one_dark(Color::Green))),
-            Span::raw("Navigate Up and Down"),
-        ]);
-
-        let enter_spans = Spans::from(vec![
-            Span::styled("ENTER ", Style::default().fg(one_dark(Color::Green))),
-            Span::raw("Load Story"),
-        ]);
-
-        let quit_spans = Spans::from(vec![
-            Span::styled("ESC   ", Style::default().fg(one_dark(Color::Green))),
-            Span::raw("Quit"),
-        ]);
-
-        let paragraph = Paragraph::new(vec![
-            back_spans,
-            enter_spans,
-            Spans::from(""),
-            arrows_spans,
-            quit_spans,
-        ])
-        .wrap(Wrap { trim: true });
-
-        f.render_widget(paragraph, help_chunks[0]);
-        // COMMANDS BOX
 
+    /// The stories table alone, in `area`. Panes that are not focused get a dimmer border.
+    pub fn draw_main(
+        &mut self,
+        f: &mut Frame<CrosstermBackend<Stdout>>,
+        area: Rect,
+        focused: bool,
+    ) {
         // STORIES LIST
-        let block = Block::default().title(self.title).borders(Borders::ALL);
+        let block = Block::default()
+            .title(match &self.category {
+                Some(category) => format!("{} #{category}", self.title),
+                None => self.title.to_string(),
+            })
+            .borders(Borders::ALL)
+            .border_style(border_style(focused));
 
-        f.render_widget(block, chunks[1]);
+        f.render_widget(block, area);
 
         let stories_chunks = Layout::default()
             .constraints(vec![Constraint::Percentage(100)])
             .margin(1)
-            .split(chunks[1]);
+            .split(area);
+
+        // Narrow screens drop the author and length, and use shorter dates
+        let compact = MenuLayout::is_narrow(area);
+        let show_reading_time = self.config.reading.reading_time_column;
+        let shown = [
+            true,
+            true,
+            !compact,
+            true,
+            !compact,
+            true,
+            show_reading_time,
+        ];
+        let widths = if compact {
+            [
+                Constraint::Length(3),
+                Constraint::Percentage(60),
+                Constraint::Length(0),
+                Constraint::Length(10),
+                Constraint::Length(0),
+                Constraint::Length(4),
+                Constraint::Length(6),
+            ]
+        } else {
+            [
+                Constraint::Percentage(4),
+                Constraint::Percentage(if show_reading_time { 44 } else { 50 }),
+                Constraint::Percentage(15),
+                Constraint::Percentage(10),
+                Constraint::Percentage(6),
+                Constraint::Percentage(5),
+                Constraint::Percentage(6),
+            ]
+        };
+        let header: Vec<&str> = [
+            "Read",
+            "Title",
+            "Author",
+            "Published",
+            "Length",
+            "Saved",
+            "Time",
+        ]
+        .into_iter()
+        .zip(shown)
+        .filter_map(|(name, shown)| shown.then_some(name))
+        .collect();
+        let widths: Vec<Constraint> = widths
+            .into_iter()
+            .zip(shown)
+            .filter_map(|(width, shown)| shown.then_some(width))
+            .collect();
 
+        let stories = self.feed.stories();
         let items: Vec<Row> = self
-            .feed
-            .stories()
+            .visible
             .iter()
-            .map(|s| {
+            .map(|&i| {
+                let s = &stories[i];
                 let read = if s.read { "[#]" } else { "" };
                 let title = s.title().unwrap_or_default();
                 let creator = s.creator().unwrap_or_default();
                 let author = s.creator().unwrap_or_default();
                 let author = if creator.is_empty() { author } else { creator };
-                let published = s.pub_date().unwrap_or_default();
+                let published = match s.published() {
+                    Some(published) if compact => published.format("%Y-%m-%d").to_string(),
+                    _ => s.pub_date().unwrap_or_default().to_string(),
+                };
                 let color = if s.read {
                     one_dark(Color::Gray)
                 } else {
                     Color::White
                 };
-                Row::new(vec![
+                let mut title_spans = vec![];
+                if s.starred {
+                    title_spans.push(Span::styled(
+                        "* ",
+                        Style::default().fg(one_dark(Color::Yellow)),
+                    ));
+                }
+                if s.is_podcast() {
+                    title_spans.push(Span::styled(
+                        "♫ ",
+                        Style::default().fg(one_dark(Color::Magenta)),
+                    ));
+                }
+                title_spans.push(Span::raw(title));
+                if let Some(feeds) = self.also_in.get(i).filter(|feeds| !feeds.is_empty()) {
+                    title_spans.push(Span::styled(
+                        format!(" (also in: {})", feeds.join(", ")),
+                        Style::default().fg(one_dark(Color::Gray)),
+                    ));
+                }
+                let duration = s.duration_secs().map(format_duration).unwrap_or_default();
+                let saved = match self.downloader.state(s) {
+                    Some(DownloadState::Finished(_)) => String::from("[#]"),
+                    Some(DownloadState::Failed(_)) => String::from("[!]"),
+                    Some(state) => match state.percent() {
+                        Some(percent) => format!("{percent}%"),
+                        None => String::from("..."),
+                    },
+                    None if s.download().is_some() => String::from("[#]"),
+                    None => String::new(),
+                };
+                let minutes = self.reading_minutes.get(i).copied().unwrap_or_default();
+                let reading_time = if minutes > 0 {
+                    format!("{minutes} min")
+                } else {
+                    String::new()
+                };
+                let cells: Vec<Cell> = [
                     Cell::from(read),
-                    Cell::from(title),
+                    Cell::from(Spans::from(title_spans)),
                     Cell::from(author),
                     Cell::from(published),
-                ])
-                .style(Style::default().fg(color))
+                    Cell::from(duration),
+                    Cell::from(saved),
+                    Cell::from(reading_time),
+                ]
+                .into_iter()
+                .zip(shown)
+                .filter_map(|(cell, shown)| shown.then_some(cell))
+                .collect();
+                Row::new(cells).style(Style::default().fg(color))
             })
             .collect();
 
         let table = Table::new(items)
-            .header(
-                Row::new(vec!["Read", "Title", "Author", "Published"])
-                    .style(Style::default().add_modifier(Modifier::BOLD)),
-            )
-            .widths(&[
-                Constraint::Percentage(4),
-                Constraint::Percentage(50),
-                Constraint::Percentage(15),
-                Constraint::Percentage(10),
-            ])
-            .column_spacing(5)
+            .header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)))
+            .widths(&widths)
+            .column_spacing(if compact { 1 } else { 5 })
             .highlight_style(
                 Style::default()
                     .bg(one_dark(Color::LightBlue))
                     .fg(one_dark(Color::Black)),
             );
 
+        // The header takes the first row
+        let area = stories_chunks[0];
+        self.rows_area = Rect {
+            y: area.y + 1,
+            height: area.height.saturating_sub(1),
+            ..area
+        };
+        self.offset = list_offset(self.offset, self.state.selected(), self.rows_area.height);
         f.render_stateful_widget(table, stories_chunks[0], &mut self.state);
+        // STORIES LIST
     }
 
-    fn transition(&mut self, key_event: KeyEvent) -> MenuState {
-        match key_event.code {
-            KeyCode::Esc => {
-                return MenuState::Exit;
-            }
+    /// Does what was picked in the command palette
+    fn choose(&mut self, choice: Choice) -> crate::error::Result<MenuState> {
+        match choice {
+            Choice::Key(code) => self.transition(KeyEvent::from(code)),
+            Choice::Feed(id) => match find_one_feed(Some(doc! {"_id": id}), self.db)? {
+                Some(feed) => Ok(MenuState::Stories(Some(Box::new(feed)))),
+                None => Ok(self.state()),
+            },
+        }
+    }
 
-            KeyCode::Up => {
-                self.previous();
-            }
+    /// Whether the help or a popup is open
+    pub fn has_overlay(&self) -> bool {
+        self.help || self.category_popup.popped || self.command_palette.popped
+    }
+
+    /// The help and popups, over everything else
+    pub fn draw_overlays(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
+        if self.help {
+            draw_help(f, Self::ACTIONS);
+        }
+
+        // POPUP
+        if self.category_popup.popped {
+            self.category_popup.draw(f);
+        } else if self.command_palette.popped {
+            self.command_palette.draw(f);
+        }
+        // POPUP
+    }
+}
 
-            KeyCode::Down => {
-                self.next();
+impl<'a> Menu for StoriesMenu<'a> {
+    fn draw(&mut self, f: &mut Frame<CrosstermBackend<Stdout>>) {
+        let background = Block::default().style(Style::default().bg(one_dark(Color::Black)));
+        f.render_widget(background, f.size());
+
+        let layout = MenuLayout::new(f.size());
+        self.draw_main(f, layout.main, true);
+        draw_commands(f, &layout, Self::ACTIONS);
+        self.draw_overlays(f);
+    }
+
+    fn transition(&mut self, key_event: KeyEvent) -> crate::error::Result<MenuState> {
+        if self.category_popup.popped {
+            self.category_popup.transition(key_event)?;
+            self.take_category()?;
+            return Ok(self.state());
+        }
+        if self.command_palette.popped {
+            self.command_palette.transition(key_event)?;
+            return match self.command_palette.take_chosen() {
+                Some(choice) => self.choose(choice),
+                None => Ok(self.state()),
+            };
+        }
+        if self.help {
+            // Any of the keys that usually close something closes the help
+            if let KeyCode::Esc | KeyCode::Enter | KeyCode::Char('?') = key_event.code {
+                self.help = false;
             }
+            return Ok(self.state());
+        }
 
-            KeyCode::Left => {
-                return MenuState::Feeds;
+        if opens_palette(&key_event) {
+            let feeds = find_many_feed(None, self.db)?;
+            self.command_palette.open(Self::ACTIONS, &feeds);
+        } else if let Some(run) = action_for(Self::ACTIONS, key_event.code) {
+            return run(self);
+        } else {
+            match key_event.code {
+                KeyCode::Up => self.previous(),
+                KeyCode::Down => self.next(),
+                _ => {}
             }
+        }
+        // Fallback if none of the keys were pressed
+        Ok(self.state())
+    }
 
-            KeyCode::Enter => {
-                if let Some(selected) = self.state.selected() {
-                    if let Some(story) = self.feed.stories().get(selected) {
-                        // Function for setting a story as read
-                        update_one_feed(
-                            doc! {
-                                "stories._id": story.id
-                            },
-                            doc! {
-                                "$set": {
-                                    "stories.$.read": true
-                                }
-                            },
-                            self.db,
-                        )
-                        .unwrap();
-
-                        return MenuState::Contents(Some(story.clone()));
+    fn mouse(&mut self, mouse_event: MouseEvent) -> crate::error::Result<MenuState> {
+        if self.category_popup.popped {
+            self.category_popup.mouse(mouse_event)?;
+            self.take_category()?;
+            return Ok(self.state());
+        }
+        if self.command_palette.popped {
+            return Ok(self.state());
+        }
+
+        match mouse_event.kind {
+            MouseEventKind::ScrollDown => self.next(),
+            MouseEventKind::ScrollUp => self.previous(),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let clicked = item_at(
+                    self.rows_area,
+                    self.offset,
+                    mouse_event.column,
+                    mouse_event.row,
+                )
+                .filter(|i| *i < self.visible.len());
+                if let Some(i) = clicked {
+                    self.state.select(Some(i));
+                    if self.clicks.double(i) {
+                        return self.transition(KeyEvent::from(KeyCode::Enter));
                     }
                 }
             }
             _ => {}
         }
-        // Fallback if none of the keys were pressed
-        self.state()
+        Ok(self.state())
     }
 
     fn reload(&mut self) -> crate::error::Result<()> {
-        self.feed = find_one_feed(Some(doc! {"_id": self.feed.id}), self.db)?.unwrap();
+        self.feed =
+            find_one_feed(Some(doc! {"_id": self.feed.id}), self.db)?.ok_or(Error::FeedNotFound)?;
+        self.filter_by_category();
+        self.count_reading_minutes();
+        self.duplicates_stale = true;
         Ok(())
     }
 
@@ -236,5 +703,20 @@ impl<'a> Menu for StoriesMenu<'a> {
         MenuState::Stories(None)
     }
 
-    fn observer(&mut self) {}
+    fn observer(&mut self) -> crate::error::Result<()> {
+        if self.duplicates_stale {
+            self.find_duplicates()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_help_shows_the_keys_that_run_something() {
+        crate::menus::tests::assert_actions_match_keys(StoriesMenu::ACTIONS);
+    }
 }
@@ -1,5 +1,5 @@
 use tui::backend::CrosstermBackend;
-use tui::layout::{Constraint, Layout};
+use tui::layout::{Constraint, Layout, Rect};
 use tui::style::{Color, Modifier, Style};
 use tui::text::{Span, Spans};
 use tui::widgets::{Block, Borders, Paragraph, Tabs, Wrap};
@@ -7,17 +7,21 @@ use tui::Frame;
 
 use std::io::Stdout;
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 
 use crate::{models::Feed, util::fetch_feed};
 
-use super::{centered_rect, one_dark, Menu, MenuState};
+use super::{centered_rect, one_dark, tab_at, Menu, MenuState};
+
+const BUTTONS: [&str; 2] = ["Back", "Subscribe"];
 
 pub struct SubscribePopup<'a> {
     title: &'a str,
     feed: Option<Feed>,
 
     pub popped: bool,
+    /// Where the buttons were drawn, for the mouse
+    buttons: Rect,
     pub subscribed: bool,
     fetched: bool,
 
@@ -30,6 +34,7 @@ impl<'a> SubscribePopup<'a> {
         SubscribePopup {
             title: "Search for a Feed Online",
             popped: false,
+            buttons: Rect::default(),
             subscribed: false,
             fetched: false,
             feed: None,
@@ -117,7 +122,7 @@ impl<'a> Menu for SubscribePopup<'a> {
                 f.render_widget(description, feed_chunks[1]);
             }
 
-            let tabs = Tabs::new(vec![Spans::from("Back"), Spans::from("Subscribe")])
+            let tabs = Tabs::new(BUTTONS.iter().map(|button| Spans::from(*button)).collect())
                 .select(self.choice as usize)
                 .style(Style::default().fg(one_dark(Color::Green)))
                 .highlight_style(
@@ -126,15 +131,16 @@ impl<'a> Menu for SubscribePopup<'a> {
                         .bg(one_dark(Color::Green))
                         .fg(one_dark(Color::DarkGray)),
                 );
+            self.buttons = feed_chunks[2];
             f.render_widget(tabs, feed_chunks[2]);
         }
         f.render_widget(input_container, chunks[0]);
     }
 
-    fn transition(&mut self, key_event: KeyEvent) -> MenuState {
+    fn transition(&mut self, key_event: KeyEvent) -> crate::error::Result<MenuState> {
         match key_event.code {
             KeyCode::Esc => {
-                self.reload().unwrap();
+                self.reload()?;
             }
 
             KeyCode::Char(c) => {
@@ -157,25 +163,41 @@ impl<'a> Menu for SubscribePopup<'a> {
 
             KeyCode::Enter => {
                 if !self.fetched {
-                    self.feed = match fetch_feed(&self.input) {
+                    self.set_feed(match fetch_feed(&self.input) {
                         Ok(feed) => Some(feed),
                         Err(error) => {
                             let mut feed_error = Feed::default();
-                            feed_error.set_description(error.to_string());
+                            let hint = if error.is_retryable() {
+                                " (this might be temporary, try again later)"
+                            } else {
+                                ""
+                            };
+                            feed_error.set_description(format!("{error}{hint}"));
                             Some(feed_error)
                         }
-                    };
+                    });
                     self.fetched = true;
                 } else {
                     self.subscribed = self.choice;
-                    self.reload().unwrap();
+                    self.reload()?;
                 }
             }
 
             _ => {}
         }
         // Fallback if none of the keys were pressed
-        self.state()
+        Ok(self.state())
+    }
+
+    fn mouse(&mut self, mouse_event: MouseEvent) -> crate::error::Result<MenuState> {
+        if let MouseEventKind::Down(MouseButton::Left) = mouse_event.kind {
+            let clicked = tab_at(&BUTTONS, self.buttons, mouse_event.column, mouse_event.row);
+            if let (Some(index), true) = (clicked, self.fetched) {
+                self.choice = index == 1;
+                return self.transition(KeyEvent::from(KeyCode::Enter));
+            }
+        }
+        Ok(self.state())
     }
 
     fn reload(&mut self) -> crate::error::Result<()> {
@@ -190,5 +212,7 @@ impl<'a> Menu for SubscribePopup<'a> {
         MenuState::Feeds
     }
 
-    fn observer(&mut self) {}
+    fn observer(&mut self) -> crate::error::Result<()> {
+        Ok(())
+    }
 }
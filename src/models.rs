@@ -1,10 +1,22 @@
+mod duplicates;
+mod enclosure;
 mod feed;
+mod feed_info;
+mod image;
+mod source;
 mod story;
 
+pub use duplicates::Duplicates;
+pub use enclosure::Enclosure;
 pub use feed::Feed;
-pub use story::Story;
+pub use feed_info::FeedInfo;
+pub use image::Image;
+pub use source::Source;
+pub use story::{ContentFormat, Story};
 
+use mongodb::bson::oid::ObjectId;
 use mongodb::bson::Document;
+use mongodb::bson::{doc, to_bson};
 use mongodb::results::{DeleteResult, InsertManyResult, InsertOneResult, UpdateResult};
 use mongodb::sync::Database;
 
@@ -53,3 +65,45 @@ pub fn update_one_feed(
     db.collection::<Feed>("feeds")
         .update_one(query, update, None)
 }
+
+pub fn replace_one_feed(feed: &Feed, db: &Database) -> mongodb::error::Result<UpdateResult> {
+    db.collection::<Feed>("feeds")
+        .replace_one(doc! {"_id": feed.id}, feed, None)
+}
+
+/// Stores the content of a story, leaving what else changed since it was read alone
+pub fn update_story_content(story: &Story, db: &Database) -> mongodb::error::Result<UpdateResult> {
+    update_one_feed(
+        doc! {"stories._id": story.id},
+        doc! {"$set": {
+            "stories.$.content": story.content(),
+            "stories.$.content_format": to_bson(&story.content_format())?,
+            "stories.$.full_text": story.full_text(),
+        }},
+        db,
+    )
+}
+
+pub fn mark_one_story_read(story: &Story, db: &Database) -> mongodb::error::Result<UpdateResult> {
+    update_one_feed(
+        doc! {"stories._id": story.id},
+        doc! {"$set": {"stories.$.read": true}},
+        db,
+    )
+}
+
+pub fn mark_one_story_unread(id: ObjectId, db: &Database) -> mongodb::error::Result<UpdateResult> {
+    update_one_feed(
+        doc! {"stories._id": id},
+        doc! {"$set": {"stories.$.read": false}},
+        db,
+    )
+}
+
+pub fn mark_feed_read(feed: &Feed, db: &Database) -> mongodb::error::Result<UpdateResult> {
+    update_one_feed(
+        doc! {"_id": feed.id},
+        doc! {"$set": {"stories.$[].read": true}},
+        db,
+    )
+}
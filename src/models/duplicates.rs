@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use mongodb::bson::oid::ObjectId;
+
+use super::{Feed, Story};
+
+/// Titles with fewer words, like "Episode 12", are shared by unrelated stories
+const MIN_TITLE_WORDS: usize = 4;
+
+/// Share of the words of two titles they need to have in common to be the same article
+const SIMILAR_TITLES: f64 = 0.75;
+
+/// Where the same article can be found, for feeds that syndicate each other
+#[derive(Default)]
+pub struct Duplicates {
+    /// Feeds having a story, by what makes it the same article
+    feeds: HashMap<String, Vec<(ObjectId, String)>>,
+    /// The words of every title long enough to compare, and the feed having the story
+    titles: Vec<(Vec<String>, ObjectId, String)>,
+    /// Positions in `titles` of the titles having a word, by the word
+    words: HashMap<String, Vec<usize>>,
+}
+
+impl Duplicates {
+    pub fn new(feeds: &[Feed]) -> Self {
+        let mut duplicates = Duplicates::default();
+        for feed in feeds {
+            duplicates.add(feed);
+        }
+        duplicates
+    }
+
+    /// Takes in the stories of a feed, again after it was refreshed
+    pub fn add(&mut self, feed: &Feed) {
+        for story in feed.stories() {
+            for key in story.duplicate_keys() {
+                let feeds = self.feeds.entry(key).or_default();
+                if !feeds.iter().any(|(id, _)| *id == feed.id) {
+                    feeds.push((feed.id, feed.title().to_string()));
+                }
+            }
+
+            let words = story.title_words();
+            if words.len() >= MIN_TITLE_WORDS {
+                for word in &words {
+                    self.words
+                        .entry(word.clone())
+                        .or_default()
+                        .push(self.titles.len());
+                }
+                self.titles.push((words, feed.id, feed.title().to_string()));
+            }
+        }
+    }
+
+    /// The feeds having the story by its GUID or its link, or by a title much like its own
+    fn feeds_with(&self, story: &Story) -> Vec<(ObjectId, &str)> {
+        let mut feeds: Vec<(ObjectId, &str)> = story
+            .duplicate_keys()
+            .iter()
+            .filter_map(|key| self.feeds.get(key))
+            .flatten()
+            .map(|(id, title)| (*id, title.as_str()))
+            .collect();
+
+        let words = story.title_words();
+        if words.len() >= MIN_TITLE_WORDS {
+            let mut shared: HashMap<usize, usize> = HashMap::new();
+            for word in &words {
+                for title in self.words.get(word).into_iter().flatten() {
+                    *shared.entry(*title).or_default() += 1;
+                }
+            }
+            for (title, shared) in shared {
+                let (other, id, feed_title) = &self.titles[title];
+                let all = words.len() + other.len() - shared;
+                if shared as f64 >= SIMILAR_TITLES * all as f64 {
+                    feeds.push((*id, feed_title));
+                }
+            }
+        }
+        feeds
+    }
+
+    /// Titles of the feeds other than `feed` that have the story as well
+    pub fn also_in(&self, story: &Story, feed: &Feed) -> Vec<String> {
+        let mut titles: Vec<String> = self
+            .feeds_with(story)
+            .into_iter()
+            .filter(|(id, _)| *id != feed.id)
+            .map(|(_, title)| title.to_string())
+            .collect();
+        titles.sort();
+        titles.dedup();
+        titles
+    }
+
+    /// New stories another feed already brought arrive read, so they are only read once.
+    /// Returns how many there were.
+    pub fn mark_read(&self, feed: &mut Feed, added: usize) -> usize {
+        let mut marked = 0;
+        let id = feed.id;
+        for story in feed.stories_mut().iter_mut().take(added) {
+            let elsewhere = self.feeds_with(story).iter().any(|(other, _)| *other != id);
+            if elsewhere && !story.read {
+                story.read = true;
+                marked += 1;
+            }
+        }
+        marked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(title: &str, story_titles: &[&str]) -> Feed {
+        let mut feed = Feed::default();
+        feed.id = ObjectId::new();
+        feed.set_title(title);
+        feed.set_stories(
+            story_titles
+                .iter()
+                .map(|title| {
+                    Story::from(
+                        rss::ItemBuilder::default()
+                            .title(Some(title.to_string()))
+                            .build(),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        );
+        feed
+    }
+
+    #[test]
+    fn titles_written_a_little_differently_are_the_same_story() {
+        let wire = feed("Wire", &["Apple Releases New MacBook Pro With M4 Chip"]);
+        let blog = feed(
+            "Blog",
+            &[
+                "Apple releases new MacBook Pro with M4 chips",
+                "Apple releases new iPad",
+                "Episode 12",
+            ],
+        );
+        let duplicates = Duplicates::new(&[wire.clone(), blog.clone()]);
+
+        assert_eq!(duplicates.also_in(&blog.stories()[0], &blog), vec!["Wire"]);
+        assert!(duplicates.also_in(&blog.stories()[1], &blog).is_empty());
+        assert!(duplicates.also_in(&blog.stories()[2], &blog).is_empty());
+        assert_eq!(duplicates.also_in(&wire.stories()[0], &wire), vec!["Blog"]);
+    }
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct Enclosure {
+    url: String,
+    mime_type: String,
+    length: Option<u64>,
+}
+
+impl From<rss::Enclosure> for Enclosure {
+    fn from(enclosure: rss::Enclosure) -> Self {
+        Self {
+            // Feeds put all sorts of things in here, zero usually means unknown
+            length: enclosure
+                .length
+                .trim()
+                .parse()
+                .ok()
+                .filter(|length| *length > 0),
+            url: enclosure.url,
+            mime_type: enclosure.mime_type,
+        }
+    }
+}
+
+impl Enclosure {
+    pub fn url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    pub fn set_url(&mut self, url: impl Into<String>) {
+        self.url = url.into();
+    }
+
+    pub fn mime_type(&self) -> &str {
+        self.mime_type.as_str()
+    }
+
+    pub fn set_mime_type(&mut self, mime_type: impl Into<String>) {
+        self.mime_type = mime_type.into();
+    }
+
+    pub fn length(&self) -> Option<u64> {
+        self.length
+    }
+
+    pub fn set_length(&mut self, length: impl Into<Option<u64>>) {
+        self.length = length.into();
+    }
+
+    /// Audio and video are what podcast players deal with
+    pub fn is_media(&self) -> bool {
+        self.mime_type.starts_with("audio/") || self.mime_type.starts_with("video/")
+    }
+}
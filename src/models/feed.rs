@@ -2,7 +2,11 @@ use serde::{Deserialize, Serialize};
 
 use mongodb::bson::oid::ObjectId;
 
-use super::Story;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::config::Retention;
+
+use super::{ContentFormat, FeedInfo, Story};
 
 #[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub struct Feed {
@@ -13,6 +17,27 @@ pub struct Feed {
     rss_link: String,
     description: String,
     stories: Vec<Story>,
+    #[serde(default)]
+    auto_download: usize,
+    #[serde(default)]
+    full_text: bool,
+    /// Shown instead of the title the feed gives itself
+    #[serde(default)]
+    custom_title: Option<String>,
+    /// Refreshing all feeds skips this one until this many minutes have passed
+    #[serde(default)]
+    refresh_minutes: Option<u32>,
+    /// When the feed was last refreshed, in seconds since the epoch
+    #[serde(default)]
+    refreshed_at: Option<i64>,
+    #[serde(default)]
+    notify: bool,
+    /// Place in the feeds list when it is sorted by hand, feeds without one go last
+    #[serde(default)]
+    position: Option<i64>,
+    /// Updated on every refresh, like the title
+    #[serde(default)]
+    info: FeedInfo,
 }
 
 impl From<rss::Channel> for Feed {
@@ -22,6 +47,7 @@ impl From<rss::Channel> for Feed {
             .iter()
             .map(|i| Story::from(i.to_owned()))
             .collect();
+        let info = FeedInfo::from(&channel);
 
         Self {
             id: ObjectId::new(),
@@ -30,12 +56,26 @@ impl From<rss::Channel> for Feed {
             rss_link: String::new(),
             description: channel.description,
             stories,
+            auto_download: 0,
+            full_text: false,
+            custom_title: None,
+            refresh_minutes: None,
+            refreshed_at: None,
+            notify: false,
+            position: None,
+            info,
         }
     }
 }
 
 impl Feed {
+    /// The custom title if there is one, otherwise the feed's own
     pub fn title(&self) -> &str {
+        self.custom_title.as_deref().unwrap_or(self.title.as_str())
+    }
+
+    /// The title the feed gives itself
+    pub fn fetched_title(&self) -> &str {
         self.title.as_str()
     }
 
@@ -75,7 +115,320 @@ impl Feed {
         self.stories = stories.into();
     }
 
+    pub fn stories_mut(&mut self) -> &mut [Story] {
+        &mut self.stories
+    }
+
     pub fn stories_len(&self) -> usize {
         self.stories.len()
     }
+
+    /// How many of the newest episodes get downloaded after a refresh
+    pub fn auto_download(&self) -> usize {
+        self.auto_download
+    }
+
+    pub fn set_auto_download(&mut self, auto_download: usize) {
+        self.auto_download = auto_download;
+    }
+
+    /// Whether the full article is fetched for every new story
+    pub fn full_text(&self) -> bool {
+        self.full_text
+    }
+
+    pub fn set_full_text(&mut self, full_text: bool) {
+        self.full_text = full_text;
+    }
+
+    pub fn custom_title(&self) -> Option<&str> {
+        self.custom_title.as_deref()
+    }
+
+    pub fn set_custom_title(&mut self, custom_title: Option<String>) {
+        self.custom_title = custom_title;
+    }
+
+    /// Minutes to wait between refreshes, every refresh fetches the feed without it
+    pub fn refresh_minutes(&self) -> Option<u32> {
+        self.refresh_minutes
+    }
+
+    pub fn set_refresh_minutes(&mut self, refresh_minutes: Option<u32>) {
+        self.refresh_minutes = refresh_minutes;
+    }
+
+    pub fn set_refreshed(&mut self, now: DateTime<Utc>) {
+        self.refreshed_at = Some(now.timestamp());
+    }
+
+    /// Why the feed is not refreshed now: the refresh interval has not passed since the
+    /// last refresh, or the feed asks to be skipped now. `None` when it is due. Without
+    /// an interval of our own the feed's `ttl` is waited for. A feed that was never refreshed is
+    /// always due.
+    pub fn not_due(&self, now: DateTime<Utc>) -> Option<String> {
+        let refreshed_at = self.refreshed_at?;
+        let wait = match (self.refresh_minutes, self.info.ttl()) {
+            (Some(minutes), _) => Some((minutes, format!("refreshes every {minutes} minutes"))),
+            (None, Some(ttl)) => Some((
+                ttl,
+                format!("asks to wait {ttl} minutes between fetches (ttl)"),
+            )),
+            (None, None) => None,
+        };
+        if let Some((minutes, rule)) = wait {
+            let next = refreshed_at + i64::from(minutes) * 60;
+            if now.timestamp() < next {
+                let next = Utc
+                    .timestamp_opt(next, 0)
+                    .single()
+                    .map(|next| format!(", next after {}", next.format("%H:%M UTC")))
+                    .unwrap_or_default();
+                return Some(format!("{rule}{next}"));
+            }
+        }
+        self.info.skipped(now)
+    }
+
+    /// Whether a desktop notification is sent when a refresh brings new stories
+    pub fn notify(&self) -> bool {
+        self.notify
+    }
+
+    pub fn set_notify(&mut self, notify: bool) {
+        self.notify = notify;
+    }
+
+    pub fn position(&self) -> Option<i64> {
+        self.position
+    }
+
+    pub fn set_position(&mut self, position: Option<i64>) {
+        self.position = position;
+    }
+
+    pub fn info(&self) -> &FeedInfo {
+        &self.info
+    }
+
+    pub fn set_info(&mut self, info: FeedInfo) {
+        self.info = info;
+    }
+
+    pub fn unread_count(&self) -> usize {
+        self.stories.iter().filter(|story| !story.read).count()
+    }
+
+    /// When the newest story with a usable date was published
+    pub fn last_published(&self) -> Option<DateTime<Utc>> {
+        self.stories
+            .iter()
+            .filter_map(|story| story.published())
+            .max()
+    }
+
+    /// Takes over the stories of a freshly fetched copy of this feed that we did not
+    /// have yet, keeping the read state of the old ones. Returns how many were new.
+    pub fn merge(&mut self, fetched: Feed) -> usize {
+        let mut stories = vec![];
+        for story in fetched.stories {
+            match self
+                .stories
+                .iter_mut()
+                .find(|old| old.is_same_story(&story))
+            {
+                Some(old) => {
+                    old.fill_item_details(&story);
+                    // Stories saved as text get their HTML back, so they render like new ones
+                    if old.content_format() == ContentFormat::Text {
+                        old.set_content(story.content().map(String::from));
+                        old.set_content_format(story.content_format());
+                    }
+                }
+                None => stories.push(story),
+            }
+        }
+        let added = stories.len();
+
+        self.title = fetched.title;
+        self.link = fetched.link;
+        self.description = fetched.description;
+        self.info = fetched.info;
+
+        // Newest stories come first
+        stories.append(&mut self.stories);
+        self.stories = stories;
+        added
+    }
+
+    /// Takes over where episodes were downloaded to from `stored`, for stories that
+    /// were downloaded after this copy of the feed was read
+    pub fn keep_downloads(&mut self, stored: &Feed) {
+        for story in self
+            .stories
+            .iter_mut()
+            .filter(|story| story.download().is_none())
+        {
+            let download = stored
+                .stories
+                .iter()
+                .find(|other| other.id == story.id)
+                .and_then(|other| other.download());
+            if let Some(download) = download {
+                story.set_download(download.to_string());
+            }
+        }
+    }
+
+    /// Removes the stories the retention policy does not want to keep.
+    /// Returns how many were removed.
+    pub fn prune(&mut self, retention: &Retention, now: DateTime<Utc>) -> usize {
+        let protected = |story: &Story| {
+            (retention.keep_unread && !story.read) || (retention.keep_starred && story.starred)
+        };
+        let before = self.stories.len();
+
+        if let Some(days) = retention.max_age_days {
+            let cutoff = now - Duration::days(days);
+            // Stories without a usable date can not be judged by age
+            self.stories.retain(|story| {
+                protected(story) || story.published().is_none_or(|date| date >= cutoff)
+            });
+        }
+
+        if let Some(max_stories) = retention.max_stories {
+            let mut excess = self.stories.len().saturating_sub(max_stories);
+            let mut index = self.stories.len();
+            // Oldest stories are at the back
+            while excess > 0 && index > 0 {
+                index -= 1;
+                if !protected(&self.stories[index]) {
+                    self.stories.remove(index);
+                    excess -= 1;
+                }
+            }
+        }
+
+        before - self.stories.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn story(title: &str, guid: Option<&str>, link: Option<&str>) -> Story {
+        let mut story = Story::default();
+        story.set_title(title.to_string());
+        story.set_guid(guid.map(String::from));
+        story.set_link(link.map(String::from));
+        story
+    }
+
+    fn feed(stories: Vec<Story>) -> Feed {
+        let mut feed = Feed::default();
+        feed.set_stories(stories);
+        feed
+    }
+
+    fn titles(feed: &Feed) -> Vec<&str> {
+        feed.stories()
+            .iter()
+            .map(|story| story.title().unwrap_or_default())
+            .collect()
+    }
+
+    fn retention(max_stories: Option<usize>, max_age_days: Option<i64>) -> Retention {
+        Retention {
+            max_stories,
+            max_age_days,
+            keep_unread: true,
+            keep_starred: true,
+        }
+    }
+
+    #[test]
+    fn protected_stories_survive_max_stories() {
+        // Newest first, every story read but the oldest one, the second oldest is starred
+        let mut stories: Vec<Story> = ["five", "four", "three", "two", "one"]
+            .into_iter()
+            .map(|title| story(title, None, None))
+            .collect();
+        for story in &mut stories {
+            story.read = true;
+        }
+        stories[3].starred = true;
+        stories[4].read = false;
+        let mut feed = feed(stories);
+
+        let pruned = feed.prune(&retention(Some(2), None), Utc::now());
+        assert_eq!(pruned, 3);
+        assert_eq!(titles(&feed), vec!["two", "one"]);
+    }
+
+    #[test]
+    fn undated_stories_survive_max_age_days() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+        let mut stories = vec![
+            story("recent", None, None),
+            story("undated", None, None),
+            story("unreadable date", None, None),
+            story("old", None, None),
+        ];
+        stories[0].set_pub_date(String::from("Thu, 29 Feb 2024 08:00:00 GMT"));
+        stories[2].set_pub_date(String::from("last week"));
+        stories[3].set_pub_date(String::from("Mon, 01 Jan 2024 08:00:00 GMT"));
+        for story in &mut stories {
+            story.read = true;
+        }
+        let mut feed = feed(stories);
+
+        let pruned = feed.prune(&retention(None, Some(30)), now);
+        assert_eq!(pruned, 1);
+        assert_eq!(titles(&feed), vec!["recent", "undated", "unreadable date"]);
+    }
+
+    #[test]
+    fn a_guid_match_wins_over_a_changed_link() {
+        let mut old = story(
+            "Story",
+            Some("tag:example.com,2024:1"),
+            Some("https://a.com/1"),
+        );
+        old.read = true;
+        let mut feed = feed(vec![old]);
+
+        let fetched = self::feed(vec![
+            story(
+                "Story, Updated",
+                Some("tag:example.com,2024:1"),
+                Some("https://a.com/story-1"),
+            ),
+            story(
+                "Other",
+                Some("tag:example.com,2024:2"),
+                Some("https://a.com/1"),
+            ),
+        ]);
+        assert_eq!(feed.merge(fetched), 1);
+        assert_eq!(titles(&feed), vec!["Other", "Story"]);
+        assert!(feed.stories()[1].read);
+    }
+
+    #[test]
+    fn a_legacy_story_without_a_guid_matches_by_link() {
+        let mut old = story("Story", None, Some("https://a.com/1"));
+        old.read = true;
+        let mut feed = feed(vec![old]);
+
+        let fetched = self::feed(vec![story(
+            "Story",
+            Some("tag:example.com,2024:1"),
+            Some("https://a.com/1"),
+        )]);
+        assert_eq!(feed.merge(fetched), 0);
+        assert!(feed.stories()[0].read);
+        // Later refreshes can tell it apart by the GUID
+        assert_eq!(feed.stories()[0].guid(), Some("tag:example.com,2024:1"));
+    }
 }
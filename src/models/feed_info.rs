@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+
+use chrono::{DateTime, Datelike, Timelike, Utc};
+
+use super::Image;
+
+/// What a channel tells about itself besides its title, and the hints it gives
+/// aggregators about when to fetch it
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct FeedInfo {
+    language: Option<String>,
+    image: Option<Image>,
+    last_build_date: Option<String>,
+    /// Minutes the channel can be cached before it is fetched again
+    ttl: Option<u32>,
+    /// Hours of the day in GMT, 0 to 23, when the channel should not be fetched
+    skip_hours: Vec<u32>,
+    /// Days of the week, by their English name, when the channel should not be fetched
+    skip_days: Vec<String>,
+    generator: Option<String>,
+    managing_editor: Option<String>,
+}
+
+impl From<&rss::Channel> for FeedInfo {
+    fn from(channel: &rss::Channel) -> Self {
+        let text = |value: Option<&str>| {
+            value
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(String::from)
+        };
+
+        Self {
+            language: text(channel.language()),
+            image: channel
+                .image()
+                .filter(|image| !image.url().trim().is_empty())
+                .map(|image| Image::new(image.url().trim(), text(Some(image.title())))),
+            last_build_date: text(channel.last_build_date()),
+            ttl: channel
+                .ttl()
+                .and_then(|ttl| ttl.trim().parse().ok())
+                .filter(|ttl| *ttl > 0),
+            // Some channels write midnight as 24
+            skip_hours: channel
+                .skip_hours()
+                .iter()
+                .filter_map(|hour| hour.trim().parse::<u32>().ok())
+                .filter(|hour| *hour <= 24)
+                .map(|hour| hour % 24)
+                .collect(),
+            skip_days: channel
+                .skip_days()
+                .iter()
+                .map(|day| day.trim().to_string())
+                .filter(|day| !day.is_empty())
+                .collect(),
+            generator: text(channel.generator()),
+            managing_editor: text(channel.managing_editor()),
+        }
+    }
+}
+
+impl FeedInfo {
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// The logo of the channel
+    pub fn image(&self) -> Option<&Image> {
+        self.image.as_ref()
+    }
+
+    pub fn last_build_date(&self) -> Option<&str> {
+        self.last_build_date.as_deref()
+    }
+
+    /// When the content of the channel last changed, if the date can be read
+    pub fn last_built(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc2822(self.last_build_date()?)
+            .ok()
+            .map(|date| date.with_timezone(&Utc))
+    }
+
+    pub fn ttl(&self) -> Option<u32> {
+        self.ttl
+    }
+
+    pub fn skip_hours(&self) -> &[u32] {
+        &self.skip_hours
+    }
+
+    pub fn skip_days(&self) -> &[String] {
+        &self.skip_days
+    }
+
+    pub fn generator(&self) -> Option<&str> {
+        self.generator.as_deref()
+    }
+
+    pub fn managing_editor(&self) -> Option<&str> {
+        self.managing_editor.as_deref()
+    }
+
+    /// Whether the channel asked not to be fetched at this hour or on this day
+    pub fn skips(&self, now: DateTime<Utc>) -> bool {
+        self.skipped(now).is_some()
+    }
+
+    /// Why the channel should not be fetched now, if it asked for that
+    pub fn skipped(&self, now: DateTime<Utc>) -> Option<String> {
+        if self.skip_hours.contains(&now.hour()) {
+            return Some(format!(
+                "asks to be skipped at {:02}:00 GMT (skipHours)",
+                now.hour()
+            ));
+        }
+        let day = now.weekday().to_string();
+        self.skip_days
+            .iter()
+            .find(|skip| {
+                skip.get(..3)
+                    .is_some_and(|skip| skip.eq_ignore_ascii_case(&day))
+            })
+            .map(|skip| format!("asks to be skipped on {skip} (skipDays)"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone;
+
+    fn info(skip_hours: &[&str], skip_days: &[&str]) -> FeedInfo {
+        let channel = rss::ChannelBuilder::default()
+            .skip_hours(
+                skip_hours
+                    .iter()
+                    .map(|hour| hour.to_string())
+                    .collect::<Vec<_>>(),
+            )
+            .skip_days(
+                skip_days
+                    .iter()
+                    .map(|day| day.to_string())
+                    .collect::<Vec<_>>(),
+            )
+            .build();
+        FeedInfo::from(&channel)
+    }
+
+    /// 2024-01-06 was a Saturday
+    fn saturday(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 6, hour, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn midnight_written_as_24_is_skipped() {
+        let info = info(&["24", "7", "25", "soon"], &[]);
+        assert_eq!(info.skip_hours(), &[0, 7]);
+        assert!(info.skips(saturday(0)));
+        assert!(info.skips(saturday(7)));
+        assert!(!info.skips(saturday(8)));
+    }
+
+    #[test]
+    fn days_match_by_their_first_letters() {
+        for day in ["Saturday", "saturday", "Sat"] {
+            assert!(info(&[], &[day]).skips(saturday(12)), "{day}");
+        }
+        assert!(!info(&[], &["Sunday", "Sa"]).skips(saturday(12)));
+    }
+
+    #[test]
+    fn skipping_says_why() {
+        assert_eq!(
+            info(&["7"], &["Saturday"]).skipped(saturday(7)).as_deref(),
+            Some("asks to be skipped at 07:00 GMT (skipHours)")
+        );
+        assert_eq!(
+            info(&[], &["Saturday"]).skipped(saturday(8)).as_deref(),
+            Some("asks to be skipped on Saturday (skipDays)")
+        );
+    }
+}
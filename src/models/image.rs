@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// A picture that goes with a story, from Media RSS or an `<img>` in its content
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct Image {
+    url: String,
+    alt: Option<String>,
+}
+
+impl Image {
+    pub fn new(url: impl Into<String>, alt: Option<String>) -> Self {
+        Image {
+            url: url.into(),
+            alt: alt.filter(|alt| !alt.trim().is_empty()),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    pub fn set_url(&mut self, url: impl Into<String>) {
+        self.url = url.into();
+    }
+
+    /// Text describing the image, for when it can not be seen
+    pub fn alt(&self) -> Option<&str> {
+        self.alt.as_deref()
+    }
+
+    pub fn set_alt(&mut self, alt: impl Into<Option<String>>) {
+        self.alt = alt.into().filter(|alt| !alt.trim().is_empty());
+    }
+}
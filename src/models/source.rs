@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// The feed a story was taken from, when the feed republishes other feeds
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct Source {
+    url: String,
+    title: Option<String>,
+}
+
+impl From<rss::Source> for Source {
+    fn from(source: rss::Source) -> Self {
+        Self {
+            url: source.url,
+            title: source.title,
+        }
+    }
+}
+
+impl Source {
+    pub fn url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    pub fn set_url(&mut self, url: impl Into<String>) {
+        self.url = url.into();
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn set_title(&mut self, title: impl Into<Option<String>>) {
+        self.title = title.into();
+    }
+
+    /// The title, or the URL for sources without one
+    pub fn name(&self) -> &str {
+        self.title().unwrap_or(self.url())
+    }
+}
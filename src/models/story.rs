@@ -1,10 +1,25 @@
-use std::io::{BufReader, Cursor};
-
 use serde::{Deserialize, Serialize};
 
 use mongodb::bson::oid::ObjectId;
 
-use html2text::render::text_renderer::PlainDecorator;
+use chrono::{DateTime, Utc};
+
+use crate::util::normalize_url;
+
+use std::collections::BTreeMap;
+
+use rss::extension::Extension;
+
+use super::{Enclosure, Image, Source};
+
+/// How the content of a story is stored. Stories saved before the HTML was kept
+/// only have the text it was turned into, which is what the default stands for.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ContentFormat {
+    #[default]
+    Text,
+    Html,
+}
 
 #[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub struct Story {
@@ -18,7 +33,40 @@ pub struct Story {
     author: Option<String>,
     creator: Option<String>,
     content: Option<String>,
+    #[serde(default)]
+    content_format: ContentFormat,
+    /// The content is the full article from the story's link, not what the feed had
+    #[serde(default)]
+    full_text: bool,
+    #[serde(default)]
+    enclosure: Option<Enclosure>,
+    #[serde(default)]
+    duration: Option<String>,
+    #[serde(default)]
+    episode: Option<String>,
+    #[serde(default)]
+    season: Option<String>,
+    #[serde(default)]
+    episode_type: Option<String>,
+    /// What the feed calls the item, which stays the same when its link changes
+    #[serde(default)]
+    guid: Option<String>,
+    #[serde(default)]
+    categories: Vec<String>,
+    /// Link to the page with the comments on the story
+    #[serde(default)]
+    comments: Option<String>,
+    #[serde(default)]
+    source: Option<Source>,
+    /// Pictures from Media RSS, the ones in the content stay there
+    #[serde(default)]
+    images: Vec<Image>,
+    /// Where the enclosure was downloaded to
+    #[serde(default)]
+    download: Option<String>,
     pub read: bool,
+    #[serde(default)]
+    pub starred: bool,
     pub scroll: usize,
 }
 
@@ -30,11 +78,14 @@ impl From<rss::Item> for Story {
             None
         };
 
-        let html = BufReader::new(Cursor::new(item.content().unwrap_or_default()));
-        let html = html2text::parse(html);
-        let html = html.render(200, PlainDecorator::new()).into_string();
+        let itunes = item.itunes_ext().cloned().unwrap_or_default();
+        let images = item
+            .extensions()
+            .get("media")
+            .map(media_images)
+            .unwrap_or_default();
 
-        let story = Self {
+        Self {
             id: ObjectId::new(),
             title: item.title,
             link: item.link,
@@ -42,11 +93,33 @@ impl From<rss::Item> for Story {
             pub_date: item.pub_date,
             author: item.author,
             creator,
-            content: Some(html),
+            // Kept as HTML, it is rendered for the width of the screen when read
+            content: item.content,
+            content_format: ContentFormat::Html,
+            full_text: false,
+            enclosure: item.enclosure.map(Enclosure::from),
+            duration: itunes.duration,
+            episode: itunes.episode,
+            season: itunes.season,
+            episode_type: itunes.episode_type,
+            guid: item
+                .guid
+                .map(|guid| guid.value)
+                .filter(|guid| !guid.trim().is_empty()),
+            categories: item
+                .categories
+                .into_iter()
+                .map(|category| category.name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .collect(),
+            comments: item.comments,
+            source: item.source.map(Source::from),
+            images,
+            download: None,
             read: false,
+            starred: false,
             scroll: 0,
-        };
-        story
+        }
     }
 }
 
@@ -83,6 +156,14 @@ impl Story {
         self.pub_date = pub_date.into();
     }
 
+    /// The publishing date, if the feed gave us one that makes sense
+    pub fn published(&self) -> Option<DateTime<Utc>> {
+        let pub_date = self.pub_date()?;
+        DateTime::parse_from_rfc2822(pub_date.trim())
+            .ok()
+            .map(|date| date.with_timezone(&Utc))
+    }
+
     pub fn author(&self) -> Option<&str> {
         self.author.as_deref()
     }
@@ -106,4 +187,430 @@ impl Story {
     pub fn set_content(&mut self, content: impl Into<Option<String>>) {
         self.content = content.into();
     }
+
+    pub fn content_format(&self) -> ContentFormat {
+        self.content_format
+    }
+
+    pub fn set_content_format(&mut self, content_format: ContentFormat) {
+        self.content_format = content_format;
+    }
+
+    pub fn full_text(&self) -> bool {
+        self.full_text
+    }
+
+    /// Replaces the content with the full article fetched from the story's link
+    pub fn set_full_text(&mut self, article: impl Into<String>) {
+        self.content = Some(article.into());
+        self.content_format = ContentFormat::Html;
+        self.full_text = true;
+    }
+
+    pub fn enclosure(&self) -> Option<&Enclosure> {
+        self.enclosure.as_ref()
+    }
+
+    pub fn set_enclosure(&mut self, enclosure: impl Into<Option<Enclosure>>) {
+        self.enclosure = enclosure.into();
+    }
+
+    pub fn duration(&self) -> Option<&str> {
+        self.duration.as_deref()
+    }
+
+    pub fn set_duration(&mut self, duration: impl Into<Option<String>>) {
+        self.duration = duration.into();
+    }
+
+    pub fn episode(&self) -> Option<&str> {
+        self.episode.as_deref()
+    }
+
+    pub fn set_episode(&mut self, episode: impl Into<Option<String>>) {
+        self.episode = episode.into();
+    }
+
+    pub fn season(&self) -> Option<&str> {
+        self.season.as_deref()
+    }
+
+    pub fn set_season(&mut self, season: impl Into<Option<String>>) {
+        self.season = season.into();
+    }
+
+    pub fn episode_type(&self) -> Option<&str> {
+        self.episode_type.as_deref()
+    }
+
+    pub fn set_episode_type(&mut self, episode_type: impl Into<Option<String>>) {
+        self.episode_type = episode_type.into();
+    }
+
+    pub fn guid(&self) -> Option<&str> {
+        self.guid.as_deref()
+    }
+
+    pub fn set_guid(&mut self, guid: impl Into<Option<String>>) {
+        self.guid = guid.into();
+    }
+
+    pub fn categories(&self) -> &[String] {
+        &self.categories
+    }
+
+    pub fn set_categories(&mut self, categories: impl Into<Vec<String>>) {
+        self.categories = categories.into();
+    }
+
+    pub fn has_category(&self, category: &str) -> bool {
+        self.categories
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(category))
+    }
+
+    pub fn comments(&self) -> Option<&str> {
+        self.comments.as_deref()
+    }
+
+    pub fn set_comments(&mut self, comments: impl Into<Option<String>>) {
+        self.comments = comments.into();
+    }
+
+    pub fn source(&self) -> Option<&Source> {
+        self.source.as_ref()
+    }
+
+    pub fn set_source(&mut self, source: impl Into<Option<Source>>) {
+        self.source = source.into();
+    }
+
+    pub fn images(&self) -> &[Image] {
+        &self.images
+    }
+
+    pub fn set_images(&mut self, images: impl Into<Vec<Image>>) {
+        self.images = images.into();
+    }
+
+    /// Takes the item details stories saved before they were kept from a fresh copy
+    pub fn fill_item_details(&mut self, fetched: &Story) {
+        if self.guid.is_none() {
+            self.guid = fetched.guid.clone();
+        }
+        if self.categories.is_empty() {
+            self.categories = fetched.categories.clone();
+        }
+        if self.comments.is_none() {
+            self.comments = fetched.comments.clone();
+        }
+        if self.source.is_none() {
+            self.source = fetched.source.clone();
+        }
+        if self.images.is_empty() {
+            self.images = fetched.images.clone();
+        }
+    }
+
+    pub fn download(&self) -> Option<&str> {
+        self.download.as_deref()
+    }
+
+    pub fn set_download(&mut self, download: impl Into<Option<String>>) {
+        self.download = download.into();
+    }
+
+    /// A story with playable media attached, like a podcast episode
+    pub fn is_podcast(&self) -> bool {
+        self.enclosure()
+            .is_some_and(|enclosure| enclosure.is_media())
+    }
+
+    /// The iTunes duration in seconds. Feeds use plain seconds, `MM:SS` and `HH:MM:SS`.
+    pub fn duration_secs(&self) -> Option<u64> {
+        let mut secs = 0;
+        for part in self.duration()?.trim().split(':') {
+            secs = secs * 60 + part.trim().parse::<u64>().ok()?;
+        }
+        Some(secs)
+    }
+
+    /// Whether both stories come from the same item of a feed. The GUID says so when
+    /// both have one, links and titles of items can change.
+    pub fn is_same_story(&self, other: &Story) -> bool {
+        if let (Some(guid), Some(other_guid)) = (self.guid(), other.guid()) {
+            return guid == other_guid;
+        }
+        match (self.link(), other.link()) {
+            (Some(link), Some(other_link)) => link == other_link,
+            _ => self.title().is_some() && self.title() == other.title(),
+        }
+    }
+
+    /// What this story has in common with copies of it in other feeds: its GUID and its
+    /// link without tracking parameters. Titles are compared by their words instead, see
+    /// `title_words`.
+    pub fn duplicate_keys(&self) -> Vec<String> {
+        let mut keys = vec![];
+        // Short GUIDs are counters, which say nothing outside of their feed
+        if let Some(guid) = self.guid().filter(|guid| guid.len() >= 16) {
+            let guid = if guid.contains("://") {
+                normalize_url(guid)
+            } else {
+                guid.to_string()
+            };
+            keys.push(format!("guid:{guid}"));
+        }
+        if let Some(link) = self.link().filter(|link| !link.trim().is_empty()) {
+            keys.push(format!("link:{}", normalize_url(link)));
+        }
+        keys
+    }
+
+    /// The words of the title in lower case, each once, so titles that were written a
+    /// little differently can be compared
+    pub fn title_words(&self) -> Vec<String> {
+        let mut words: Vec<String> = self
+            .title()
+            .unwrap_or_default()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        words.sort();
+        words.dedup();
+        words
+    }
+
+    /// Words in the story's content, or in its description when it has none
+    pub fn word_count(&self) -> usize {
+        let (text, format) = match self.content() {
+            Some(content) if !content.trim().is_empty() => (content, self.content_format()),
+            _ => (self.description().unwrap_or_default(), ContentFormat::Html),
+        };
+        match format {
+            ContentFormat::Html => strip_tags(text).split_whitespace().count(),
+            ContentFormat::Text => text.split_whitespace().count(),
+        }
+    }
+
+    /// Minutes it takes to read the story at `words_per_minute`, rounded up
+    pub fn reading_minutes(&self, words_per_minute: usize) -> usize {
+        self.word_count().div_ceil(words_per_minute.max(1))
+    }
+}
+
+/// Images in the Media RSS elements of an item: `media:thumbnail`, `media:content` that
+/// is an image, and both of those inside `media:group`
+fn media_images(media: &BTreeMap<String, Vec<Extension>>) -> Vec<Image> {
+    let mut images: Vec<Image> = vec![];
+    for (name, extensions) in media {
+        for extension in extensions {
+            let image = match name.as_str() {
+                "group" => {
+                    for image in media_images(extension.children()) {
+                        if !images.iter().any(|other| other.url() == image.url()) {
+                            images.push(image);
+                        }
+                    }
+                    continue;
+                }
+                "thumbnail" => true,
+                "content" => {
+                    let attrs = extension.attrs();
+                    attrs.get("medium").is_some_and(|medium| medium == "image")
+                        || attrs
+                            .get("type")
+                            .is_some_and(|mime_type| mime_type.starts_with("image/"))
+                }
+                _ => false,
+            };
+            let url = extension.attrs().get("url").filter(|url| !url.is_empty());
+            if let (true, Some(url)) = (image, url) {
+                // The alt text of Media RSS is the description or title of the media
+                let alt = ["description", "title"].iter().find_map(|child| {
+                    extension
+                        .children()
+                        .get(*child)?
+                        .first()?
+                        .value()
+                        .map(String::from)
+                });
+                if !images.iter().any(|other| other.url() == url) {
+                    images.push(Image::new(url.as_str(), alt));
+                }
+            }
+        }
+    }
+    images
+}
+
+/// The text of an HTML fragment, tags are replaced by spaces so words stay apart
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rss::extension::ExtensionBuilder;
+
+    fn story(guid: Option<&str>, link: Option<&str>, title: &str) -> Story {
+        let guid = guid.map(|guid| rss::GuidBuilder::default().value(guid).build());
+        Story::from(
+            rss::ItemBuilder::default()
+                .guid(guid)
+                .link(link.map(String::from))
+                .title(Some(title.to_string()))
+                .build(),
+        )
+    }
+
+    #[test]
+    fn copies_in_other_feeds_share_keys() {
+        let story = story(
+            Some("https://example.com/?p=12345"),
+            Some("https://www.example.com/2024/story/?utm_medium=rss"),
+            "A Story Told in Two Feeds",
+        );
+        assert_eq!(
+            story.duplicate_keys(),
+            vec!["guid:example.com?p=12345", "link:example.com/2024/story",]
+        );
+        assert_eq!(
+            story.title_words(),
+            vec!["a", "feeds", "in", "story", "told", "two"]
+        );
+    }
+
+    #[test]
+    fn short_guids_are_no_keys() {
+        let story = story(Some("12"), None, "Episode 12");
+        assert!(story.duplicate_keys().is_empty());
+    }
+
+    fn media(name: &str, attrs: &[(&str, &str)], children: Vec<Extension>) -> Extension {
+        let mut grouped: BTreeMap<String, Vec<Extension>> = BTreeMap::new();
+        for child in children {
+            let local = child.name().trim_start_matches("media:").to_string();
+            grouped.entry(local).or_default().push(child);
+        }
+        ExtensionBuilder::default()
+            .name(format!("media:{name}"))
+            .attrs(
+                attrs
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect::<BTreeMap<_, _>>(),
+            )
+            .children(grouped)
+            .build()
+    }
+
+    fn text(name: &str, value: &str) -> Extension {
+        ExtensionBuilder::default()
+            .name(format!("media:{name}"))
+            .value(Some(value.to_string()))
+            .build()
+    }
+
+    fn story_with_media(extensions: Vec<Extension>) -> Story {
+        let mut media: BTreeMap<String, Vec<Extension>> = BTreeMap::new();
+        for extension in extensions {
+            let local = extension.name().trim_start_matches("media:").to_string();
+            media.entry(local).or_default().push(extension);
+        }
+        let mut item = rss::ItemBuilder::default().build();
+        item.set_extensions(BTreeMap::from([(String::from("media"), media)]));
+        Story::from(item)
+    }
+
+    fn images(story: &Story) -> Vec<(&str, Option<&str>)> {
+        story
+            .images()
+            .iter()
+            .map(|image| (image.url(), image.alt()))
+            .collect()
+    }
+
+    #[test]
+    fn media_images_are_thumbnails_and_image_content() {
+        let story = story_with_media(vec![
+            media("thumbnail", &[("url", "https://a.com/thumb.jpg")], vec![]),
+            media(
+                "content",
+                &[("url", "https://a.com/cat.jpg"), ("medium", "image")],
+                vec![text("description", "A cat"), text("title", "Cat")],
+            ),
+            media(
+                "content",
+                &[("url", "https://a.com/chart.png"), ("type", "image/png")],
+                vec![text("title", "Chart")],
+            ),
+            media(
+                "content",
+                &[("url", "https://a.com/episode.mp3"), ("type", "audio/mpeg")],
+                vec![],
+            ),
+            media(
+                "content",
+                &[("url", "https://a.com/clip.mp4"), ("medium", "video")],
+                vec![],
+            ),
+            media("content", &[("medium", "image")], vec![]),
+        ]);
+        assert_eq!(
+            images(&story),
+            vec![
+                ("https://a.com/cat.jpg", Some("A cat")),
+                ("https://a.com/chart.png", Some("Chart")),
+                ("https://a.com/thumb.jpg", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn media_groups_are_looked_into_once_per_image() {
+        let story = story_with_media(vec![
+            media(
+                "content",
+                &[("url", "https://a.com/large.jpg"), ("medium", "image")],
+                vec![],
+            ),
+            media(
+                "group",
+                &[],
+                vec![
+                    media(
+                        "content",
+                        &[("url", "https://a.com/large.jpg"), ("medium", "image")],
+                        vec![],
+                    ),
+                    media("thumbnail", &[("url", "https://a.com/small.jpg")], vec![]),
+                ],
+            ),
+            media("thumbnail", &[("url", "https://a.com/small.jpg")], vec![]),
+        ]);
+        assert_eq!(
+            images(&story),
+            vec![
+                ("https://a.com/large.jpg", None),
+                ("https://a.com/small.jpg", None),
+            ]
+        );
+    }
 }
@@ -0,0 +1,38 @@
+use std::cell::{Cell, RefCell};
+
+/// What menus want the app to know about things they did: a message for the status
+/// bar, like the theme that was switched to, and that a program had the terminal.
+/// The app takes both after every step.
+pub struct Notifier {
+    message: RefCell<Option<String>>,
+    terminal_lent: Cell<bool>,
+}
+
+impl Notifier {
+    pub fn new() -> Self {
+        Notifier {
+            message: RefCell::new(None),
+            terminal_lent: Cell::new(false),
+        }
+    }
+
+    /// Has the status bar tell `message`, a newer message replaces one not shown yet
+    pub fn tell(&self, message: impl Into<String>) {
+        *self.message.borrow_mut() = Some(message.into());
+    }
+
+    /// The message told last, once
+    pub fn take_message(&self) -> Option<String> {
+        self.message.borrow_mut().take()
+    }
+
+    /// A program ran in the terminal, whatever was on screen has to be drawn again
+    pub fn lent_terminal(&self) {
+        self.terminal_lent.set(true);
+    }
+
+    /// Whether a program had the terminal since the last call
+    pub fn take_terminal_lent(&self) -> bool {
+        self.terminal_lent.take()
+    }
+}
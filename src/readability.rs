@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use html5ever::serialize::{serialize, SerializeOpts, TraversalScope};
+use html5ever::tendril::TendrilSink;
+use html5ever::{local_name, parse_document};
+use markup5ever_rcdom::{Handle, NodeData, RcDom, SerializableHandle};
+
+use url::Url;
+
+/// Elements that never belong to the article itself
+const REMOVED_TAGS: &[&str] = &[
+    "script", "style", "noscript", "iframe", "form", "button", "input", "select", "textarea",
+    "nav", "header", "footer", "aside", "svg", "canvas",
+];
+
+/// Words in a class or id hinting at page furniture rather than the article
+const NEGATIVE: &[&str] = &[
+    "comment",
+    "sidebar",
+    "footer",
+    "footnote",
+    "masthead",
+    "menu",
+    "nav",
+    "share",
+    "social",
+    "advert",
+    "sponsor",
+    "promo",
+    "related",
+    "subscribe",
+    "newsletter",
+    "cookie",
+    "banner",
+    "popup",
+    "modal",
+    "widget",
+    "breadcrumb",
+    "meta",
+    "tags",
+];
+
+/// Words in a class or id hinting at the article
+const POSITIVE: &[&str] = &[
+    "article", "body", "content", "entry", "main", "page", "post", "story", "text",
+];
+
+/// Less text than this is more likely a teaser than an article
+const MIN_ARTICLE_LENGTH: usize = 250;
+
+/// Finds the main article in a web page, like browser reader modes do: paragraphs score
+/// points for the element holding them, and the best scoring element wins. Links and
+/// images are made absolute using `base`. Returns the HTML of the article.
+pub fn extract_article(html: &str, base: Option<&str>) -> Option<String> {
+    let dom = parse_document(RcDom::default(), Default::default())
+        .from_utf8()
+        .read_from(&mut html.as_bytes())
+        .ok()?;
+
+    let base = base.and_then(|base| Url::parse(base).ok());
+    clean(&dom.document, base.as_ref());
+
+    let mut candidates = HashMap::new();
+    score(&dom.document, &mut vec![], &mut candidates);
+
+    let (best, _) = candidates
+        .into_values()
+        .map(|(handle, score)| {
+            let score = score * (1.0 - link_density(&handle));
+            (handle, score)
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+    if text(&best).trim().len() < MIN_ARTICLE_LENGTH {
+        return None;
+    }
+
+    let mut bytes = vec![];
+    let opts = SerializeOpts {
+        traversal_scope: TraversalScope::ChildrenOnly(None),
+        ..Default::default()
+    };
+    serialize(&mut bytes, &SerializableHandle::from(best), opts).ok()?;
+    String::from_utf8(bytes).ok()
+}
+
+/// Drops everything that is not content and makes links absolute
+fn clean(node: &Handle, base: Option<&Url>) {
+    node.children
+        .borrow_mut()
+        .retain(|child| !is_unlikely(child));
+
+    if let (NodeData::Element { name, attrs, .. }, Some(base)) = (&node.data, base) {
+        let attribute = match name.local {
+            local_name!("a") => Some(local_name!("href")),
+            local_name!("img") => Some(local_name!("src")),
+            _ => None,
+        };
+        if let Some(attribute) = attribute {
+            for attr in attrs.borrow_mut().iter_mut() {
+                if attr.name.local == attribute {
+                    if let Ok(url) = base.join(&attr.value) {
+                        attr.value = url.as_str().into();
+                    }
+                }
+            }
+        }
+    }
+
+    for child in node.children.borrow().iter() {
+        clean(child, base);
+    }
+}
+
+fn is_unlikely(node: &Handle) -> bool {
+    match &node.data {
+        NodeData::Comment { .. } => true,
+        NodeData::Element { name, .. } => {
+            if REMOVED_TAGS.contains(&name.local.as_ref()) {
+                return true;
+            }
+            let hints = class_and_id(node);
+            // Never drop the body or the article because of its class
+            let structural = matches!(
+                name.local,
+                local_name!("html") | local_name!("body") | local_name!("article")
+            );
+            !structural
+                && NEGATIVE.iter().any(|word| hints.contains(word))
+                && !POSITIVE.iter().any(|word| hints.contains(word))
+        }
+        _ => false,
+    }
+}
+
+/// Gives points for every paragraph to its parent, and half of them to its grandparent
+fn score(
+    node: &Handle,
+    ancestors: &mut Vec<Handle>,
+    candidates: &mut HashMap<usize, (Handle, f64)>,
+) {
+    if let Some(tag) = tag(node) {
+        if matches!(tag, "p" | "pre" | "td") {
+            let text = text(node);
+            let length = text.trim().len();
+            if length >= 25 {
+                let points = 1.0 + text.matches(',').count() as f64 + (length / 100).min(3) as f64;
+                let mut parents = ancestors.iter().rev();
+                for (share, parent) in [1.0, 0.5].into_iter().zip(parents.by_ref()) {
+                    let key = Rc::as_ptr(parent) as usize;
+                    let candidate = candidates
+                        .entry(key)
+                        .or_insert_with(|| (parent.clone(), initial_score(parent)));
+                    candidate.1 += points * share;
+                }
+            }
+        }
+    }
+
+    ancestors.push(node.clone());
+    for child in node.children.borrow().iter() {
+        score(child, ancestors, candidates);
+    }
+    ancestors.pop();
+}
+
+fn initial_score(node: &Handle) -> f64 {
+    let by_tag = match tag(node).unwrap_or_default() {
+        "article" => 10.0,
+        "div" | "section" | "main" => 5.0,
+        "pre" | "td" | "blockquote" => 3.0,
+        "ol" | "ul" | "dl" | "dd" | "dt" | "li" => -3.0,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+        _ => 0.0,
+    };
+
+    let hints = class_and_id(node);
+    let by_class = if NEGATIVE.iter().any(|word| hints.contains(word)) {
+        -25.0
+    } else if POSITIVE.iter().any(|word| hints.contains(word)) {
+        25.0
+    } else {
+        0.0
+    };
+    by_tag + by_class
+}
+
+/// How much of the text inside a node is link text
+fn link_density(node: &Handle) -> f64 {
+    let length = text(node).len();
+    if length == 0 {
+        return 1.0;
+    }
+
+    fn link_text(node: &Handle) -> usize {
+        if tag(node) == Some("a") {
+            return text(node).len();
+        }
+        node.children.borrow().iter().map(link_text).sum()
+    }
+    link_text(node) as f64 / length as f64
+}
+
+fn tag(node: &Handle) -> Option<&str> {
+    match &node.data {
+        NodeData::Element { name, .. } => Some(name.local.as_ref()),
+        _ => None,
+    }
+}
+
+fn class_and_id(node: &Handle) -> String {
+    match &node.data {
+        NodeData::Element { attrs, .. } => attrs
+            .borrow()
+            .iter()
+            .filter(|attr| matches!(attr.name.local, local_name!("class") | local_name!("id")))
+            .map(|attr| attr.value.to_lowercase())
+            .collect::<Vec<String>>()
+            .join(" "),
+        _ => String::new(),
+    }
+}
+
+fn text(node: &Handle) -> String {
+    let mut text = String::new();
+    fn collect(node: &Handle, text: &mut String) {
+        if let NodeData::Text { contents } = &node.data {
+            text.push_str(&contents.borrow());
+        }
+        for child in node.children.borrow().iter() {
+            collect(child, text);
+        }
+    }
+    collect(node, &mut text);
+    text
+}
@@ -0,0 +1,530 @@
+use html2text::render::text_renderer::{RichAnnotation, TaggedLine, TextDecorator};
+
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use url::Url;
+
+use crate::menus::one_dark;
+use crate::models::Image;
+
+const QUOTE_PREFIX: &str = "│ ";
+
+/// Story HTML turned into styled lines, with its links gathered as footnotes
+#[derive(Default, Clone)]
+pub struct Rendered {
+    pub lines: Vec<Spans<'static>>,
+    pub links: Vec<String>,
+    /// Images in the content first, then the ones from Media RSS
+    pub images: Vec<Image>,
+}
+
+impl Rendered {
+    /// Rows the lines take up, once they were wrapped to the width of the area
+    pub fn height(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Breaks the lines that are wider than `width` columns at spaces, so the paragraph
+    /// never wraps them again and every row is counted
+    pub fn wrap(&mut self, width: u16) {
+        let width = width.max(1) as usize;
+        self.lines = std::mem::take(&mut self.lines)
+            .into_iter()
+            .flat_map(|line| wrap_line(line, width))
+            .collect();
+    }
+
+    /// Adds the images that came with the story and lists all of them at the bottom
+    /// with their alt text, since the terminal can not show them
+    pub fn list_images(&mut self, media: &[Image]) {
+        for image in media {
+            if !self.images.iter().any(|other| other.url() == image.url()) {
+                self.images.push(image.clone());
+            }
+        }
+        if self.images.is_empty() {
+            return;
+        }
+
+        self.lines.push(Spans::from(""));
+        self.lines.push(Spans::from(Span::styled(
+            "Images",
+            Style::default()
+                .fg(one_dark(Color::Green))
+                .add_modifier(Modifier::BOLD),
+        )));
+        for image in &self.images {
+            let mut spans = vec![Span::styled(
+                "▣ ",
+                Style::default().fg(one_dark(Color::Magenta)),
+            )];
+            if let Some(alt) = image.alt() {
+                spans.push(Span::raw(format!("{alt} ")));
+            }
+            spans.push(Span::styled(
+                image.url().to_string(),
+                Style::default().fg(one_dark(Color::Blue)),
+            ));
+            self.lines.push(Spans::from(spans));
+        }
+    }
+}
+
+/// Renders `html` wrapped to `width` columns. Every link gets a `[n]` marker after its
+/// text and is listed at the bottom, so it can be opened by its number. Relative links
+/// and images are resolved against `base`, the link of the story.
+pub fn render_html(html: &str, width: usize, base: Option<&str>) -> Rendered {
+    let base = base.and_then(|base| Url::parse(base).ok());
+    // Leave room for the footnote markers that are added after wrapping
+    let wrap_width = width.saturating_sub(5).max(10);
+    let lines = html2text::parse(html.as_bytes())
+        .render(wrap_width, StyledDecorator)
+        .into_lines();
+
+    // Flatten first, a link can run on over several lines
+    let lines: Vec<Vec<(String, Vec<RichAnnotation>)>> = lines
+        .into_iter()
+        .map(|line: TaggedLine<Vec<RichAnnotation>>| {
+            line.into_tagged_strings()
+                .map(|tagged| (tagged.s, tagged.tag))
+                .collect()
+        })
+        .collect();
+
+    let mut rendered = Rendered::default();
+    // The alt text of an image can be wrapped over several pieces
+    let mut last_image: Option<&str> = None;
+    for (line_index, line) in lines.iter().enumerate() {
+        let text: String = line.iter().map(|(s, _)| s.as_str()).collect();
+        let line_style = line_style(&text, line);
+
+        let mut spans = vec![];
+        for (index, (s, annotations)) in line.iter().enumerate() {
+            spans.push(Span::styled(
+                s.clone(),
+                line_style.patch(style(annotations)),
+            ));
+
+            let src = image(annotations);
+            if let Some(src) = src {
+                let alt = s.trim_matches(|c: char| c == '[' || c == ']' || c.is_whitespace());
+                let url = resolve(base.as_ref(), src);
+                let continued = rendered
+                    .images
+                    .last_mut()
+                    .filter(|last| last_image == Some(src) && last.url() == url);
+                if let Some(last) = continued {
+                    let alt = [last.alt().unwrap_or_default(), alt].join(" ");
+                    last.set_alt(Some(alt.trim().to_string()));
+                } else if !rendered.images.iter().any(|other| other.url() == url) {
+                    rendered.images.push(Image::new(url, Some(alt.to_string())));
+                }
+            }
+            last_image = src;
+
+            if let Some(url) = link(annotations) {
+                let next = match line.get(index + 1) {
+                    Some(next) => Some(next),
+                    None => lines.get(line_index + 1).and_then(|line| line.first()),
+                };
+                let continues = next.and_then(|(_, annotations)| link(annotations)) == Some(url);
+                if !continues {
+                    let number = footnote(&mut rendered.links, &resolve(base.as_ref(), url));
+                    spans.push(Span::styled(
+                        format!("[{number}]"),
+                        Style::default().fg(one_dark(Color::Gray)),
+                    ));
+                }
+            }
+        }
+        rendered.lines.push(Spans::from(spans));
+    }
+
+    if !rendered.links.is_empty() {
+        rendered.lines.push(Spans::from(""));
+        rendered.lines.push(Spans::from(Span::styled(
+            "Links",
+            Style::default()
+                .fg(one_dark(Color::Green))
+                .add_modifier(Modifier::BOLD),
+        )));
+        for (index, url) in rendered.links.iter().enumerate() {
+            rendered.lines.push(Spans::from(vec![
+                Span::styled(
+                    format!("[{}] ", index + 1),
+                    Style::default().fg(one_dark(Color::Gray)),
+                ),
+                Span::styled(url.clone(), Style::default().fg(one_dark(Color::Blue))),
+            ]));
+        }
+    }
+    rendered
+}
+
+/// Renders text that was stored already converted from HTML, keeping its line breaks
+pub fn render_text(text: &str) -> Rendered {
+    Rendered {
+        lines: text
+            .lines()
+            .map(|line| Spans::from(line.to_string()))
+            .collect(),
+        links: vec![],
+        images: vec![],
+    }
+}
+
+/// Pieces of a line of text with their styles, and whether they are spaces
+type Word = (bool, Vec<(String, Style)>);
+
+/// Splits a line into words and runs of spaces, a word can run over several spans
+fn words(line: Spans<'static>) -> Vec<Word> {
+    let mut words: Vec<Word> = vec![];
+    for span in line.0 {
+        let mut start = 0;
+        let content = span.content.as_ref();
+        for (index, c) in content.char_indices().skip(1) {
+            let previous = content[..index].ends_with(' ');
+            if (c == ' ') != previous {
+                push_piece(&mut words, &content[start..index], span.style);
+                start = index;
+            }
+        }
+        push_piece(&mut words, &content[start..], span.style);
+    }
+    words
+}
+
+fn push_piece(words: &mut Vec<Word>, piece: &str, style: Style) {
+    if piece.is_empty() {
+        return;
+    }
+    let space = piece.starts_with(' ');
+    match words.last_mut() {
+        Some((last_space, pieces)) if *last_space == space => {
+            pieces.push((piece.to_string(), style))
+        }
+        _ => words.push((space, vec![(piece.to_string(), style)])),
+    }
+}
+
+/// Greedy word wrap of one line, words wider than the line are broken anywhere
+fn wrap_line(line: Spans<'static>, width: usize) -> Vec<Spans<'static>> {
+    if line.width() <= width {
+        return vec![line];
+    }
+
+    let mut lines = vec![];
+    let mut current: Vec<Span<'static>> = vec![];
+    let mut current_width = 0;
+    // Spaces wait for the next word, they are dropped where the line is broken
+    let mut spaces: Vec<(String, Style)> = vec![];
+    for (space, pieces) in words(line) {
+        if space && current_width > 0 {
+            spaces = pieces;
+            continue;
+        }
+
+        let spaces_width: usize = spaces.iter().map(|(piece, _)| piece.width()).sum();
+        let word_width: usize = pieces.iter().map(|(piece, _)| piece.width()).sum();
+        let pieces = if current_width > 0 && current_width + spaces_width + word_width > width {
+            lines.push(Spans::from(std::mem::take(&mut current)));
+            current_width = 0;
+            spaces.clear();
+            pieces
+        } else {
+            let mut pieces = pieces;
+            pieces.splice(0..0, spaces.drain(..));
+            pieces
+        };
+
+        for (piece, style) in pieces {
+            if current_width + piece.width() <= width {
+                current_width += piece.width();
+                current.push(Span::styled(piece, style));
+                continue;
+            }
+            let mut chunk = String::new();
+            for c in piece.chars() {
+                let char_width = c.width().unwrap_or(0);
+                if current_width + char_width > width && current_width > 0 {
+                    current.push(Span::styled(std::mem::take(&mut chunk), style));
+                    lines.push(Spans::from(std::mem::take(&mut current)));
+                    current_width = 0;
+                }
+                chunk.push(c);
+                current_width += char_width;
+            }
+            current.push(Span::styled(chunk, style));
+        }
+    }
+    if !current.is_empty() {
+        lines.push(Spans::from(current));
+    }
+    lines
+}
+
+/// Number of the footnote for `url`, the same link is only listed once
+fn footnote(links: &mut Vec<String>, url: &str) -> usize {
+    match links.iter().position(|link| link == url) {
+        Some(index) => index + 1,
+        None => {
+            links.push(url.to_string());
+            links.len()
+        }
+    }
+}
+
+/// `url` made absolute, or as written when there is nothing to resolve it against
+fn resolve(base: Option<&Url>, url: &str) -> String {
+    base.and_then(|base| base.join(url).ok())
+        .map(String::from)
+        .unwrap_or_else(|| url.to_string())
+}
+
+fn link(annotations: &[RichAnnotation]) -> Option<&str> {
+    annotations.iter().find_map(|annotation| match annotation {
+        RichAnnotation::Link(url) => Some(url.as_str()),
+        _ => None,
+    })
+}
+
+fn image(annotations: &[RichAnnotation]) -> Option<&str> {
+    annotations.iter().find_map(|annotation| match annotation {
+        RichAnnotation::Image(src) => Some(src.as_str()),
+        _ => None,
+    })
+}
+
+/// Headings and quotes can only be recognised by the prefix the decorator gave them
+fn line_style(text: &str, line: &[(String, Vec<RichAnnotation>)]) -> Style {
+    let preformatted = line.iter().any(|(_, annotations)| {
+        annotations
+            .iter()
+            .any(|annotation| matches!(annotation, RichAnnotation::Preformat(_)))
+    });
+    if preformatted {
+        return Style::default();
+    }
+
+    let hashes = text.chars().take_while(|c| *c == '#').count();
+    if hashes > 0 && text[hashes..].starts_with(' ') {
+        Style::default()
+            .fg(one_dark(Color::Yellow))
+            .add_modifier(Modifier::BOLD)
+    } else if text.starts_with(QUOTE_PREFIX) {
+        Style::default()
+            .fg(one_dark(Color::Gray))
+            .add_modifier(Modifier::ITALIC)
+    } else {
+        Style::default()
+    }
+}
+
+fn style(annotations: &[RichAnnotation]) -> Style {
+    annotations
+        .iter()
+        .fold(Style::default(), |style, annotation| match annotation {
+            RichAnnotation::Default => style,
+            RichAnnotation::Link(_) => style
+                .fg(one_dark(Color::Blue))
+                .add_modifier(Modifier::UNDERLINED),
+            RichAnnotation::Image(_) => style.fg(one_dark(Color::Magenta)),
+            RichAnnotation::Emphasis => style.add_modifier(Modifier::ITALIC),
+            RichAnnotation::Strong => style.add_modifier(Modifier::BOLD),
+            RichAnnotation::Strikeout => style.add_modifier(Modifier::CROSSED_OUT),
+            RichAnnotation::Code | RichAnnotation::Preformat(_) => style
+                .fg(one_dark(Color::Green))
+                .bg(one_dark(Color::DarkGray)),
+        })
+}
+
+/// Like html2text's own `RichDecorator`, without the `*` and backticks around text
+/// since styles do that job here
+#[derive(Clone, Copy)]
+struct StyledDecorator;
+
+impl TextDecorator for StyledDecorator {
+    type Annotation = RichAnnotation;
+
+    fn decorate_link_start(&mut self, url: &str) -> (String, Self::Annotation) {
+        (String::new(), RichAnnotation::Link(url.to_string()))
+    }
+
+    fn decorate_link_end(&mut self) -> String {
+        String::new()
+    }
+
+    fn decorate_em_start(&mut self) -> (String, Self::Annotation) {
+        (String::new(), RichAnnotation::Emphasis)
+    }
+
+    fn decorate_em_end(&mut self) -> String {
+        String::new()
+    }
+
+    fn decorate_strong_start(&mut self) -> (String, Self::Annotation) {
+        (String::new(), RichAnnotation::Strong)
+    }
+
+    fn decorate_strong_end(&mut self) -> String {
+        String::new()
+    }
+
+    fn decorate_strikeout_start(&mut self) -> (String, Self::Annotation) {
+        (String::new(), RichAnnotation::Strikeout)
+    }
+
+    fn decorate_strikeout_end(&mut self) -> String {
+        String::new()
+    }
+
+    fn decorate_code_start(&mut self) -> (String, Self::Annotation) {
+        (String::new(), RichAnnotation::Code)
+    }
+
+    fn decorate_code_end(&mut self) -> String {
+        String::new()
+    }
+
+    fn decorate_preformat_first(&mut self) -> Self::Annotation {
+        RichAnnotation::Preformat(false)
+    }
+
+    fn decorate_preformat_cont(&mut self) -> Self::Annotation {
+        RichAnnotation::Preformat(true)
+    }
+
+    fn decorate_image(&mut self, src: &str, title: &str) -> (String, Self::Annotation) {
+        (format!("[{title}]"), RichAnnotation::Image(src.to_string()))
+    }
+
+    fn header_prefix(&mut self, level: usize) -> String {
+        "#".repeat(level) + " "
+    }
+
+    fn quote_prefix(&mut self) -> String {
+        QUOTE_PREFIX.to_string()
+    }
+
+    fn unordered_item_prefix(&mut self) -> String {
+        "• ".to_string()
+    }
+
+    fn ordered_item_prefix(&mut self, i: i64) -> String {
+        format!("{i}. ")
+    }
+
+    fn make_subblock_decorator(&self) -> Self {
+        StyledDecorator
+    }
+
+    fn finalise(&mut self, _links: Vec<String>) -> Vec<TaggedLine<Self::Annotation>> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(lines: &[Spans]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| line.0.iter().map(|span| span.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn short_lines_are_kept() {
+        let lines = wrap_line(Spans::from("a short line"), 20);
+        assert_eq!(texts(&lines), ["a short line"]);
+    }
+
+    #[test]
+    fn lines_break_at_spaces() {
+        let lines = wrap_line(Spans::from("the quick brown fox jumps"), 10);
+        assert_eq!(texts(&lines), ["the quick", "brown fox", "jumps"]);
+    }
+
+    #[test]
+    fn long_words_break_anywhere() {
+        let lines = wrap_line(Spans::from("see https://example.com/a/b"), 10);
+        assert_eq!(texts(&lines), ["see", "https://ex", "ample.com/", "a/b"]);
+        assert!(lines.iter().all(|line| line.width() <= 10));
+    }
+
+    #[test]
+    fn words_over_several_spans_stay_together() {
+        let line = Spans::from(vec![
+            Span::raw("read the "),
+            Span::styled("article", Style::default().fg(Color::Blue)),
+            Span::raw("[1] now"),
+        ]);
+        let lines = wrap_line(line, 12);
+        assert_eq!(texts(&lines), ["read the", "article[1]", "now"]);
+        assert_eq!(lines[1].0[0].style.fg, Some(Color::Blue));
+    }
+
+    #[test]
+    fn height_counts_wrapped_rows() {
+        let mut rendered = render_text("one two three four five six\n\nseven");
+        rendered.wrap(9);
+        // "one two", "three", "four five", "six", the empty line and "seven"
+        assert_eq!(rendered.height(), 6);
+    }
+
+    fn images(rendered: &Rendered) -> Vec<(&str, Option<&str>)> {
+        rendered
+            .images
+            .iter()
+            .map(|image| (image.url(), image.alt()))
+            .collect()
+    }
+
+    #[test]
+    fn inline_images_are_collected_with_their_alt_text() {
+        let html = concat!(
+            r#"<p>Before <img src="/chart.png" alt="Sales by month"> after</p>"#,
+            r#"<p><img src="https://cdn.example.com/photo.jpg" alt="The team"></p>"#,
+            r#"<p><img src="../chart.png" alt="Sales again"></p>"#,
+        );
+        let mut rendered = render_html(html, 80, Some("https://example.com/blog/post"));
+        assert_eq!(
+            images(&rendered),
+            vec![
+                ("https://example.com/chart.png", Some("Sales by month")),
+                ("https://cdn.example.com/photo.jpg", Some("The team")),
+            ]
+        );
+
+        // Media RSS images come after, unless the content has them already
+        rendered.list_images(&[
+            Image::new("https://cdn.example.com/photo.jpg", None),
+            Image::new("https://example.com/thumb.jpg", Some(String::from("Thumb"))),
+        ]);
+        assert_eq!(images(&rendered).len(), 3);
+        assert_eq!(
+            images(&rendered)[2],
+            ("https://example.com/thumb.jpg", Some("Thumb"))
+        );
+        let text = texts(&rendered.lines).join("\n");
+        assert!(text.contains("Images\n▣ Sales by month https://example.com/chart.png"));
+    }
+
+    #[test]
+    fn alt_text_wrapped_over_lines_stays_one_image() {
+        let html =
+            r#"<img src="https://example.com/a.png" alt="a long description of the picture">"#;
+        let rendered = render_html(html, 20, None);
+        assert_eq!(
+            images(&rendered),
+            vec![(
+                "https://example.com/a.png",
+                Some("a long description of the picture")
+            )]
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::cell::RefCell;
+
+use mongodb::bson::doc;
+use mongodb::bson::oid::ObjectId;
+use mongodb::sync::Database;
+
+use crate::models::{
+    delete_one_feed, insert_one_feed, mark_feed_read, mark_one_story_read, mark_one_story_unread,
+    Feed, Story,
+};
+
+/// Changes kept at most, the oldest are forgotten first
+const MAX_CHANGES: usize = 50;
+
+/// A change to the database and what it takes to revert it
+enum Change {
+    /// The whole feed, stories and all
+    DeletedFeed(Box<Feed>),
+    /// Stories that were unread before, and how to call them in messages
+    Read {
+        what: String,
+        stories: Vec<ObjectId>,
+    },
+}
+
+/// The changes made while the program runs that can not be taken back otherwise,
+/// newest last. Menus make those changes through here so `u` can revert them.
+pub struct UndoStack {
+    db: Database,
+    changes: RefCell<Vec<Change>>,
+    /// What the status bar should tell about the last change, until it is taken
+    message: RefCell<Option<String>>,
+}
+
+impl UndoStack {
+    pub fn new(db: &Database) -> Self {
+        UndoStack {
+            db: db.clone(),
+            changes: RefCell::new(Vec::new()),
+            message: RefCell::new(None),
+        }
+    }
+
+    fn push(&self, change: Change) {
+        let mut changes = self.changes.borrow_mut();
+        if changes.len() == MAX_CHANGES {
+            changes.remove(0);
+        }
+        changes.push(change);
+    }
+
+    fn tell(&self, message: impl Into<String>) {
+        *self.message.borrow_mut() = Some(message.into());
+    }
+
+    /// The message about the last change or undo, once
+    pub fn take_message(&self) -> Option<String> {
+        self.message.borrow_mut().take()
+    }
+
+    pub fn delete_feed(&self, feed: &Feed) -> crate::error::Result<()> {
+        delete_one_feed(doc! {"_id": feed.id}, &self.db)?;
+        self.push(Change::DeletedFeed(Box::new(feed.clone())));
+        self.tell(format!("Deleted {}, press u to undo", feed.title()));
+        Ok(())
+    }
+
+    pub fn mark_feed_read(&self, feed: &Feed) -> crate::error::Result<()> {
+        let unread: Vec<ObjectId> = feed
+            .stories()
+            .iter()
+            .filter(|story| !story.read)
+            .map(|story| story.id)
+            .collect();
+        mark_feed_read(feed, &self.db)?;
+
+        if !unread.is_empty() {
+            let what = format!("{} stories of {}", unread.len(), feed.title());
+            self.tell(format!("Marked {what} read, press u to undo"));
+            self.push(Change::Read {
+                what,
+                stories: unread,
+            });
+        }
+        Ok(())
+    }
+
+    /// Reading a story marks it read, which can be undone but is not worth a message
+    pub fn mark_story_read(&self, story: &Story) -> crate::error::Result<()> {
+        mark_one_story_read(story, &self.db)?;
+
+        if !story.read {
+            self.push(Change::Read {
+                what: format!("\"{}\"", story.title().unwrap_or("Untitled")),
+                stories: vec![story.id],
+            });
+        }
+        Ok(())
+    }
+
+    /// Reverts the newest change. One that fails to revert stays on the stack.
+    pub fn undo(&self) -> crate::error::Result<()> {
+        let Some(change) = self.changes.borrow_mut().pop() else {
+            self.tell("Nothing to undo");
+            return Ok(());
+        };
+
+        if let Err(error) = self.revert(&change) {
+            self.push(change);
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    fn revert(&self, change: &Change) -> crate::error::Result<()> {
+        match change {
+            Change::DeletedFeed(feed) => {
+                insert_one_feed(feed, &self.db)?;
+                self.tell(format!("Restored {}", feed.title()));
+            }
+            Change::Read { what, stories } => {
+                for id in stories {
+                    mark_one_story_unread(*id, &self.db)?;
+                }
+                self.tell(format!("Marked {what} unread again"));
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,18 +1,319 @@
-use std::io::{BufReader, Cursor};
+use std::io::{stdout, Cursor, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
 
 use rss::Channel;
 
-use crate::models::Feed;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 
-pub fn fetch_http(url: &str) -> reqwest::Result<BufReader<Cursor<String>>> {
-    let body = reqwest::blocking::get(url)?.text()?;
-    Ok(BufReader::new(Cursor::new(body)))
+use chrono::Utc;
+
+use crossterm::cursor::Show;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+
+use mongodb::bson::doc;
+use mongodb::sync::Database;
+
+use url::Url;
+
+use crate::config::{Notifications, Retention};
+use crate::error::{Error, Position};
+use crate::models::{
+    find_many_feed, find_one_feed, insert_one_feed, replace_one_feed, Duplicates, Feed, Story,
+};
+use crate::readability::extract_article;
+
+pub fn fetch_http(url: &str) -> crate::error::Result<String> {
+    reqwest::blocking::get(url)
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.text())
+        .map_err(|error| Error::fetch(url, error))
 }
 
+/// How long a refresh waits before it tries a feed a second time
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
 pub fn fetch_feed(url: &str) -> crate::error::Result<Feed> {
-    let channel = fetch_http(url)?;
-    let channel = Channel::read_from(channel)?;
+    let body = fetch_http(url)?;
+
+    // Keep hold of the reader, it knows how far the parser got
+    let mut reader = Cursor::new(body.as_bytes());
+    let channel = Channel::read_from(&mut reader).map_err(|source| Error::Rss {
+        url: url.to_string(),
+        position: Position::from_offset(&body, reader.position() as usize),
+        source,
+    })?;
+
     let mut feed = Feed::from(channel);
     feed.set_rss_link(url);
     Ok(feed)
 }
+
+/// The part of a URL that stays the same however the address is written: no scheme,
+/// no `www.`, no fragment, no tracking parameters and no trailing slash
+pub fn normalize_url(url: &str) -> String {
+    let Ok(parsed) = Url::parse(url.trim()) else {
+        return url.trim().trim_end_matches('/').to_lowercase();
+    };
+
+    let host = parsed.host_str().unwrap_or_default();
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    let port = parsed
+        .port()
+        .map(|port| format!(":{port}"))
+        .unwrap_or_default();
+    let path = parsed.path().trim_end_matches('/');
+    let query: Vec<String> = parsed
+        .query_pairs()
+        .filter(|(key, _)| !key.starts_with("utm_"))
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect();
+    let query = if query.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", query.join("&"))
+    };
+    format!("{host}{port}{path}{query}")
+}
+
+/// Fetches a feed, and once more after a short wait when the failure may pass,
+/// like a timeout or a server that is busy. The wait blocks, so this is for the
+/// command line, the interface shows the error right away instead.
+pub fn fetch_feed_retrying(url: &str) -> crate::error::Result<Feed> {
+    match fetch_feed(url) {
+        Err(error) if error.is_retryable() => {
+            thread::sleep(RETRY_DELAY);
+            fetch_feed(url)
+        }
+        fetched => fetched,
+    }
+}
+
+/// Adds a feed, unless it is there already under a URL written differently
+pub fn subscribe(feed: &Feed, db: &Database) -> crate::error::Result<()> {
+    let link = normalize_url(feed.rss_link());
+    let existing = find_many_feed(None, db)?
+        .into_iter()
+        .find(|other| normalize_url(other.rss_link()) == link);
+    if let Some(existing) = existing {
+        return Err(Error::AlreadySubscribed {
+            title: existing.title().to_string(),
+        });
+    }
+    insert_one_feed(feed, db)?;
+    Ok(())
+}
+
+/// What changed in a feed after refreshing it
+pub struct RefreshReport {
+    pub added: usize,
+    /// New stories that arrived read because another feed had them already
+    pub duplicates: usize,
+    pub pruned: usize,
+}
+
+/// Downloads the page a story links to and keeps only the article in it
+pub fn fetch_article(url: &str) -> crate::error::Result<String> {
+    let page = fetch_http(url)?;
+    extract_article(&page, Some(url)).ok_or_else(|| Error::NoArticle {
+        url: url.to_string(),
+    })
+}
+
+/// Replaces the content of a story with the article on its web page
+pub fn fetch_full_text(story: &mut Story) -> crate::error::Result<()> {
+    if let Some(link) = story.link() {
+        let article = fetch_article(link)?;
+        story.set_full_text(article);
+    }
+    Ok(())
+}
+
+/// Fetches the feed again with `fetch` and merges the new stories in, then applies the
+/// retention policy and stores the feed. `duplicates` holds the stories of the other feeds.
+pub fn refresh_feed(
+    feed: &mut Feed,
+    fetch: fn(&str) -> crate::error::Result<Feed>,
+    retention: &Retention,
+    duplicates: &Duplicates,
+    db: &Database,
+) -> crate::error::Result<RefreshReport> {
+    let fetched = fetch(feed.rss_link())?;
+    // Downloads finish in the background, start from the feed as it is stored now
+    *feed = find_one_feed(Some(doc! {"_id": feed.id}), db)?.ok_or(Error::FeedNotFound)?;
+    let added = feed.merge(fetched);
+    let duplicates = duplicates.mark_read(feed, added);
+
+    // New stories come first. A page without an article keeps what the feed gave us.
+    if feed.full_text() {
+        for story in feed.stories_mut().iter_mut().take(added) {
+            let _ = fetch_full_text(story);
+        }
+    }
+
+    let now = Utc::now();
+    let pruned = feed.prune(retention, now);
+    feed.set_refreshed(now);
+
+    // Fetching the articles takes a while, keep the downloads that finished meanwhile
+    if let Some(stored) = find_one_feed(Some(doc! {"_id": feed.id}), db)? {
+        feed.keep_downloads(&stored);
+    }
+    replace_one_feed(feed, db)?;
+    Ok(RefreshReport {
+        added,
+        duplicates,
+        pruned,
+    })
+}
+
+/// The subscriptions as an OPML document, which other feed readers can import
+pub fn export_opml(feeds: &[Feed]) -> String {
+    let mut opml = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<opml version=\"2.0\">\n",
+        "  <head>\n",
+        "    <title>rss-rs subscriptions</title>\n",
+        "  </head>\n",
+        "  <body>\n",
+    ));
+    for feed in feeds {
+        let title = escape_xml(feed.title());
+        let xml_url = escape_xml(feed.rss_link());
+        let html_url = escape_xml(feed.link());
+        opml.push_str(&format!(
+            "    <outline type=\"rss\" text=\"{title}\" title=\"{title}\" \
+             xmlUrl=\"{xml_url}\" htmlUrl=\"{html_url}\"/>\n"
+        ));
+    }
+    opml.push_str("  </body>\n</opml>\n");
+    opml
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Sends a desktop notification about the new stories of a feed, if it wants them
+pub fn notify_new_stories(
+    feed: &Feed,
+    report: &RefreshReport,
+    notifications: &Notifications,
+) -> crate::error::Result<()> {
+    // Stories read elsewhere already are not news
+    let unread = report.added - report.duplicates;
+    if !feed.notify() || unread == 0 {
+        return Ok(());
+    }
+    let message = match unread {
+        1 => format!("{}: 1 new story", feed.title()),
+        added => format!("{}: {added} new stories", feed.title()),
+    };
+    spawn_detached(&notifications.command, &message)
+}
+
+/// Starts `command_line` with `arg` appended, without waiting for it or letting it
+/// write over the terminal. It is reaped in the background once it exits.
+pub fn spawn_detached(command_line: &str, arg: &str) -> crate::error::Result<()> {
+    let mut parts = command_line.split_whitespace();
+    let program = parts.next().ok_or(Error::EmptyCommand)?;
+
+    let mut child = Command::new(program)
+        .args(parts)
+        .arg(arg)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Leaves the interface and hands the terminal to `command_line` with `arg` appended,
+/// until the program exits. `mouse` tells whether mouse capture is turned on again.
+pub fn run_in_terminal(command_line: &str, arg: &str, mouse: bool) -> crate::error::Result<()> {
+    let mut parts = command_line.split_whitespace();
+    let program = parts.next().ok_or(Error::EmptyCommand)?;
+
+    disable_raw_mode()?;
+    execute!(stdout(), LeaveAlternateScreen, DisableMouseCapture, Show)?;
+    let status = Command::new(program).args(parts).arg(arg).status();
+
+    // Come back even when the program could not be started
+    enable_raw_mode()?;
+    execute!(stdout(), EnterAlternateScreen)?;
+    if mouse {
+        execute!(stdout(), EnableMouseCapture)?;
+    }
+
+    let status = status?;
+    if !status.success() {
+        return Err(Error::CommandFailed {
+            program: program.to_string(),
+            status,
+        });
+    }
+    Ok(())
+}
+
+/// Puts `text` on the clipboard with the OSC 52 escape sequence, which the terminal
+/// handles itself so it also works over SSH
+pub fn copy_to_clipboard(text: &str) -> crate::error::Result<()> {
+    let mut stdout = stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+    stdout.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opml_escapes_titles_and_urls() {
+        let mut feed = Feed::default();
+        feed.set_title("Q&A <\"Live\">");
+        feed.set_rss_link("https://example.com/feed?a=1&b=2");
+        feed.set_link("https://example.com/");
+
+        let opml = export_opml(&[feed]);
+        assert!(opml.contains(concat!(
+            r#"<outline type="rss" text="Q&amp;A &lt;&quot;Live&quot;&gt;" "#,
+            r#"title="Q&amp;A &lt;&quot;Live&quot;&gt;" "#,
+            r#"xmlUrl="https://example.com/feed?a=1&amp;b=2" htmlUrl="https://example.com/"/>"#
+        )));
+        assert!(opml.ends_with("</opml>\n"));
+    }
+
+    #[test]
+    fn urls_written_differently_normalize_alike() {
+        let normalized = normalize_url("https://example.com/feed?id=7");
+        for url in [
+            "http://example.com/feed?id=7",
+            "https://www.example.com/feed/?id=7",
+            "https://example.com/feed?id=7&utm_source=rss#top",
+            "  HTTPS://Example.com/feed?id=7 ",
+        ] {
+            assert_eq!(normalize_url(url), normalized, "{url}");
+        }
+        assert_ne!(
+            normalize_url("https://example.com:8080/feed?id=7"),
+            normalized
+        );
+        assert_ne!(normalize_url("https://example.com/feed?id=8"), normalized);
+    }
+
+    #[test]
+    fn unparsable_urls_are_only_trimmed() {
+        assert_eq!(normalize_url(" Example.com/Feed/ "), "example.com/feed");
+    }
+}